@@ -1,7 +1,37 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+/// Foreign PSP22 surface used for cross-contract calls (selectors follow `PSP22::*`).
+pub mod psp22 {
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::primitives::AccountId;
+
+    #[derive(ink::scale::Encode, ink::scale::Decode, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        SafeTransferCheckFailed(String),
+    }
+
+    #[ink::trait_definition]
+    pub trait PSP22 {
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+    }
+
+    pub type PSP22Ref = ink::contract_ref!(PSP22);
+}
+
 #[ink::contract]
 mod moo {
+    use crate::psp22::PSP22Ref;
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::hash::Blake2x256;
+    use ink::env::DefaultEnvironment;
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::scale::Decode;
     use ink::storage::Mapping;
 
     // ⬇️ Moved here from model.rs
@@ -10,13 +40,131 @@ mod moo {
         // governance / control
         pub(crate) owner_acc: AccountId,
         pub(crate) paused_flag: bool,
-        pub(crate) is_minter: Mapping<AccountId, bool>,
+        pub(crate) pause_reason: Option<u8>,
+        pub(crate) roles: Mapping<(u8, AccountId), bool>,
+        pub(crate) role_cnt: Mapping<u8, u32>,
+        pub(crate) guardians: Mapping<AccountId, bool>,
 
         // token state
+        pub(crate) decimals_u8: u8,
         pub(crate) total_supply: Balance,
         pub(crate) balances: Mapping<AccountId, Balance>,
         pub(crate) allowances: Mapping<(AccountId, AccountId), Balance>,
 
+        // delegated minting (signer, nonce) -> used
+        pub(crate) mint_nonces: Mapping<(AccountId, u64), bool>,
+        pub(crate) pause_nonces: Mapping<(AccountId, u64), bool>,
+        // next expected permit nonce per owner (sequential)
+        pub(crate) permit_nonces: Mapping<AccountId, u64>,
+
+        // external supply control
+        pub(crate) oracle_acc_opt: Option<AccountId>,
+
+        // staking (stake held at the contract's own address) and reward accrual
+        pub(crate) staked: Mapping<AccountId, Balance>,
+        pub(crate) total_staked: Balance,
+        pub(crate) staker_cnt: u32,
+        pub(crate) reward_rate_per_block: Balance,
+        pub(crate) reward_acc_val: Balance,
+        pub(crate) reward_acc_block: u32,
+        pub(crate) reward_debt: Mapping<AccountId, Balance>,
+        pub(crate) reward_owed: Mapping<AccountId, Balance>,
+
+        // transfer policy
+        pub(crate) allow_self_transfer: bool,
+
+        // balance snapshots: lazy per-account checkpoints (snapshot id, balance before change)
+        pub(crate) snapshot_id: u32,
+        pub(crate) bal_ckpt_cnt: Mapping<AccountId, u32>,
+        pub(crate) bal_ckpt: Mapping<(AccountId, u32), (u32, Balance)>,
+        pub(crate) supply_at: Mapping<u32, Balance>,
+
+        // dividends: epoch (= snapshot id) -> (native pool, claimable supply at deposit)
+        pub(crate) dividends: Mapping<u32, (Balance, Balance)>,
+        pub(crate) dividend_claimed: Mapping<(u32, AccountId), bool>,
+        // native still owed to holders across all dividend pools (not part of the reserve)
+        pub(crate) dividend_pending: Balance,
+
+        // burn routing: when set, burns move tokens to `dead_acc` and supply stays constant
+        pub(crate) burn_to_dead_flag: bool,
+        pub(crate) dead_acc: AccountId,
+        // burns may not take `total_supply` below this (0 = no floor)
+        pub(crate) supply_floor: Balance,
+
+        // longest list any batch message accepts (owner-tunable per chain)
+        pub(crate) max_batch_size_u32: u32,
+
+        // block timestamp of the last balance change per account (transfer/mint/burn)
+        pub(crate) last_activity: Mapping<AccountId, u64>,
+
+        // accounts (besides the owner) left out of `circulating_supply`; list kept bounded
+        pub(crate) excluded: Mapping<AccountId, bool>,
+        pub(crate) excluded_list: Vec<AccountId>,
+
+        // anti-dust: smallest amount `transfer`/`transfer_from` accept (0 = off)
+        pub(crate) min_transfer_amount: Balance,
+
+        // per-call ceiling on minter mints, in base units (0 = unlimited)
+        pub(crate) max_mint_per_call: Balance,
+
+        // spenders barred from using any allowance (circuit breaker for approval exploits)
+        pub(crate) blacklisted_spenders: Mapping<AccountId, bool>,
+
+        // holder set (nonzero balances), swap-remove compacted like the NFT owner sets.
+        // Costs up to two extra mapping writes whenever a balance enters or leaves zero.
+        pub(crate) holder_cnt: u32,
+        pub(crate) holder_by_index: Mapping<u32, AccountId>,
+        pub(crate) holder_index: Mapping<AccountId, u32>,
+
+        // anti-flip: received amounts can't be sent on until their unlock block (0 blocks = off);
+        // two (amount, unlock block) slots per account
+        pub(crate) receive_cooldown_blocks: u32,
+        pub(crate) cooldown_exempt_mints: bool,
+        pub(crate) receive_locks: Mapping<AccountId, [(Balance, u32); 2]>,
+
+        // fee-on-transfer: bps skimmed to `fee_recipient_acc` unless either side is exempt
+        pub(crate) transfer_fee_bps: u16,
+        pub(crate) fee_recipient_acc: AccountId,
+        // creator fee: bps of every non-exempt move credited to an NFMoo collection treasury
+        pub(crate) creator_treasury_opt: Option<AccountId>,
+        pub(crate) creator_fee_bps: u16,
+        // deflationary burn: bps of every non-exempt move destroyed (supply shrinks)
+        pub(crate) burn_bps_u16: u16,
+        // one `TransferredWithFee` per fee-paying move instead of two `Transferred`
+        pub(crate) consolidated_fee_event_flag: bool,
+        pub(crate) fee_exempt: Mapping<AccountId, bool>,
+
+        // launch gate: non-admin senders cannot transfer until the owner enables trading
+        pub(crate) trading_enabled: bool,
+
+        // compliance cap on any single balance; exempt accounts (treasury etc.) may exceed it
+        pub(crate) max_balance_per_account: Option<Balance>,
+        pub(crate) balance_cap_exempt: Mapping<AccountId, bool>,
+
+        // era emission cap (0 blocks = off): minter mints per `era_length_blocks` window
+        pub(crate) era_length_blocks: u32,
+        pub(crate) max_mint_per_era: Balance,
+        pub(crate) era_index: u32,
+        pub(crate) era_minted: Balance,
+
+        // social recovery: `recovery_threshold` guardian signatures reassign the owner (0 = off)
+        pub(crate) recovery_threshold: u32,
+        pub(crate) recovery_nonce: u64,
+
+        // lifetime spend caps on approvals: (owner, spender) -> cap / spent so far
+        pub(crate) spend_limit: Mapping<(AccountId, AccountId), Balance>,
+        pub(crate) operator_spent: Mapping<(AccountId, AccountId), Balance>,
+
+        // approval-phishing rail: cap on nonzero allowances an owner may have outstanding
+        pub(crate) max_active_allowances: Option<u32>,
+        pub(crate) active_allowance_cnt: Mapping<AccountId, u32>,
+
+        // clawback: (sender, recipient, block) -> (amount, recipient generation) reversible within
+        // the window; a recipient's generation moves on (voiding its receipts) when it spends
+        pub(crate) reversible_window_blocks: u32,
+        pub(crate) reversible: Mapping<(AccountId, AccountId, u32), (Balance, u32)>,
+        pub(crate) reversible_gen: Mapping<AccountId, (u32, bool)>,
+
         // versioning (future migrations)
         pub(crate) storage_ver_u32: u32,
     }
@@ -24,6 +172,51 @@ mod moo {
     // Error, events, type aliases (formerly in model.rs)
     pub type Result<T> = core::result::Result<T, Error>;
 
+    // `capabilities()` bits. Assignments are stable: never reuse or renumber a bit.
+    pub const CAP_METADATA: u32 = 1 << 0;
+    pub const CAP_SUPPLY_CAP: u32 = 1 << 1;
+    pub const CAP_PERMIT: u32 = 1 << 2;
+    pub const CAP_VESTING: u32 = 1 << 3;
+    pub const CAP_MINT_WITH_SIG: u32 = 1 << 4;
+    pub const CAP_GUARDIANS: u32 = 1 << 5;
+    pub const CAP_ORACLE: u32 = 1 << 6;
+    pub const CAP_RESCUE: u32 = 1 << 7;
+    pub const CAP_ROLES: u32 = 1 << 8;
+    pub const CAP_STAKING: u32 = 1 << 9;
+
+    /// `reward_rate_per_block` is paid per `REWARD_SCALE` staked units.
+    pub const REWARD_SCALE: Balance = 1_000_000_000_000;
+
+    // Pause reason codes (free-form beyond these; frontends should treat others as unspecified).
+    pub const PAUSE_REASON_UNSPECIFIED: u8 = 0;
+    pub const PAUSE_REASON_MAINTENANCE: u8 = 1;
+    pub const PAUSE_REASON_INCIDENT: u8 = 2;
+    pub const PAUSE_REASON_MIGRATION: u8 = 3;
+
+    /// Most signatures `recover_ownership` will check in one call.
+    pub const MAX_RECOVERY_SIGS: usize = 16;
+    /// Most minters `new_with_minters` grants at deploy time.
+    pub const MAX_INITIAL_MINTERS: usize = 16;
+    /// Upper bound on `burn_with_memo` payloads.
+    pub const MAX_MEMO_LEN: usize = 128;
+    /// Longest `data` `transfer_and_call` forwards to the receiver.
+    pub const MAX_CALL_DATA_LEN: usize = 1_024;
+    /// Initial `max_batch_size_u32`.
+    pub const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+    /// Most accounts `set_excluded` can exclude from circulating supply.
+    pub const MAX_EXCLUDED: usize = 32;
+    /// Highest transfer fee `set_transfer_fee` accepts (10%).
+    pub const MAX_TRANSFER_FEE_BPS: u16 = 1_000;
+    /// Highest transfer burn `set_burn_bps` accepts (10%).
+    pub const MAX_BURN_BPS: u16 = 1_000;
+
+    // Role ids for `grant_role`/`revoke_role`/`has_role`. `roles_of` sets bit `1 << id`
+    // for each held role, so these ids are stable and must never be renumbered.
+    pub const ROLE_ADMIN: u8 = 0;
+    pub const ROLE_MINTER: u8 = 1;
+    pub const ROLE_PAUSER: u8 = 2;
+    pub const ROLE_BURNER: u8 = 3;
+
     #[derive(ink::scale::Encode, ink::scale::Decode, Debug, PartialEq, Eq)]
     #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
     pub enum Error {
@@ -36,6 +229,108 @@ mod moo {
         Paused,
         NotOwner,
         AllowanceRace,
+        InvalidSignature,
+        UnauthorizedSigner,
+        NonceUsed,
+        RescueFailed,
+        OracleCapExceeded,
+        OracleCallFailed,
+        InvalidRole,
+        InsufficientStake,
+        MemoTooLong,
+        InvalidSnapshot,
+        AlreadyClaimed,
+        NativeTransferFailed,
+        BelowMinimum,
+        SupplyFloor,
+        PermitExpired,
+        MintPerCallExceeded,
+        SpenderBlacklisted,
+        Cooldown,
+        InvalidFee,
+        CallDataTooLong,
+        ReceiverRejected,
+        TradingDisabled,
+        BalanceCapExceeded,
+        EraCapExceeded,
+        RecoveryNotConfigured,
+        RecoveryThreshold,
+        SpendLimitExceeded,
+        OtherAdmins,
+        TooManyAllowances,
+        NotReversible,
+    }
+
+    impl Error {
+        /// Human-readable text for UIs. The match is exhaustive, so a new variant
+        /// cannot compile without a message.
+        pub fn message(&self) -> &'static str {
+            match self {
+                Error::AmountZero => "amount must be nonzero",
+                Error::InsufficientBalance => "insufficient balance",
+                Error::InsufficientAllowance => "insufficient allowance",
+                Error::Overflow => "arithmetic overflow",
+                Error::SameAccount => "source and destination are the same account",
+                Error::Unauthorized => "caller lacks the required role",
+                Error::Paused => "token is paused",
+                Error::NotOwner => "caller is not an admin",
+                Error::AllowanceRace => "allowance changed or must be reset to zero first",
+                Error::InvalidSignature => "invalid signature",
+                Error::UnauthorizedSigner => "signer is not authorized",
+                Error::NonceUsed => "nonce already used",
+                Error::RescueFailed => "token rescue transfer failed",
+                Error::OracleCapExceeded => "amount exceeds the oracle ceiling",
+                Error::OracleCallFailed => "supply oracle call failed",
+                Error::InvalidRole => "unknown role",
+                Error::InsufficientStake => "insufficient staked balance",
+                Error::MemoTooLong => "memo too long",
+                Error::InvalidSnapshot => "unknown snapshot id",
+                Error::AlreadyClaimed => "already claimed",
+                Error::NativeTransferFailed => "native transfer failed",
+                Error::BelowMinimum => "amount below minimum transfer",
+                Error::SupplyFloor => "burn would breach supply floor",
+                Error::PermitExpired => "permit deadline passed",
+                Error::MintPerCallExceeded => "mint exceeds per-call limit",
+                Error::SpenderBlacklisted => "spender is blacklisted",
+                Error::Cooldown => "received tokens still cooling down",
+                Error::InvalidFee => "fee out of range",
+                Error::CallDataTooLong => "call data too long",
+                Error::ReceiverRejected => "receiver rejected the transfer",
+                Error::TradingDisabled => "trading not enabled",
+                Error::BalanceCapExceeded => "recipient balance would exceed the per-account cap",
+                Error::EraCapExceeded => "mint exceeds the remaining era budget",
+                Error::RecoveryNotConfigured => "ownership recovery threshold not set",
+                Error::RecoveryThreshold => "not enough guardian signatures",
+                Error::SpendLimitExceeded => "spender's lifetime spend limit exceeded",
+                Error::OtherAdmins => "revoke the other admins first",
+                Error::TooManyAllowances => "too many active allowances",
+                Error::NotReversible => "no reversible transfer within the window",
+            }
+        }
+    }
+
+    /// One-shot governance snapshot for auditors. `schema_ver` is `storage_ver_u32`;
+    /// fields are only ever appended, and a bump signals the layout changed.
+    #[derive(ink::scale::Encode, ink::scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
+    pub struct RoleConfig {
+        pub schema_ver: u32,
+        pub owner: AccountId,
+        pub admin_cnt: u32,
+        pub minter_cnt: u32,
+        pub pauser_cnt: u32,
+        pub burner_cnt: u32,
+        pub paused: bool,
+        pub oracle: Option<AccountId>,
+        /// 0 = unlimited.
+        pub max_mint_per_call: Balance,
+        /// Era cap; `era_length_blocks == 0` means off.
+        pub era_length_blocks: u32,
+        pub max_mint_per_era: Balance,
+        pub max_balance_per_account: Option<Balance>,
+        pub supply_floor: Balance,
+        /// Ownership renounced: no admin can ever change configuration again.
+        pub renounced: bool,
     }
 
     #[ink(event)]
@@ -47,6 +342,24 @@ mod moo {
         pub(crate) amount_val: Balance,
     }
 
+    /// A move with deductions in one event: `to_acc` received `amount_val`, `fee_acc`
+    /// received `fee_val`, `burn_val` was burned and `creator_acc` received `creator_val`.
+    /// The sender paid `amount_val + fee_val + burn_val + creator_val`; no separate
+    /// `Burned` or `CreatorFeePaid` is emitted for the move.
+    #[ink(event)]
+    pub struct TransferredWithFee {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_val: Balance,
+        pub(crate) fee_val: Balance,
+        pub(crate) fee_acc: AccountId,
+        pub(crate) burn_val: Balance,
+        pub(crate) creator_val: Balance,
+        pub(crate) creator_acc: Option<AccountId>,
+    }
+
     #[ink(event)]
     pub struct Minted {
         #[ink(topic)]
@@ -82,32 +395,433 @@ mod moo {
         pub(crate) enabled_flag: bool,
     }
 
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        pub(crate) role_u8: u8,
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) by_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        pub(crate) role_u8: u8,
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) by_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct GuardianSet {
+        #[ink(topic)]
+        pub(crate) guardian_acc: AccountId,
+        pub(crate) enabled_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyPaused {
+        #[ink(topic)]
+        pub(crate) by_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OracleSet {
+        pub(crate) oracle_acc_opt: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct OracleCeilingChecked {
+        #[ink(topic)]
+        pub(crate) oracle_acc: AccountId,
+        pub(crate) ceiling_val: Balance,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TokenRescued {
+        #[ink(topic)]
+        pub(crate) token_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        pub(crate) staker_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        pub(crate) staker_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardRateSet {
+        pub(crate) rate_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardsClaimed {
+        #[ink(topic)]
+        pub(crate) staker_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BurnedWithMemo {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        pub(crate) amount_val: Balance,
+        pub(crate) memo_vec: Vec<u8>,
+    }
+
+    #[ink(event)]
+    pub struct SelfTransferPolicySet {
+        pub(crate) allowed_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        pub(crate) from_acc_opt: Option<AccountId>,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Snapshot {
+        pub(crate) snapshot_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct DividendDeposited {
+        #[ink(topic)]
+        pub(crate) epoch_u32: u32,
+        pub(crate) pool_val: Balance,
+        pub(crate) supply_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DividendClaimed {
+        #[ink(topic)]
+        pub(crate) epoch_u32: u32,
+        #[ink(topic)]
+        pub(crate) holder_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BurnModeSet {
+        pub(crate) burn_to_dead_flag: bool,
+        pub(crate) dead_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MaxBatchSizeSet {
+        pub(crate) max_batch_size_u32: u32,
+    }
+
+    #[ink(event)]
+    pub struct ExcludedSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) excluded_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct MinTransferAmountSet {
+        pub(crate) min_transfer_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SupplyFloorSet {
+        pub(crate) supply_floor: Balance,
+    }
+
+    #[ink(event)]
+    pub struct MaxMintPerCallSet {
+        pub(crate) max_mint_per_call: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SpenderBlacklisted {
+        #[ink(topic)]
+        pub(crate) spender_acc: AccountId,
+        pub(crate) blacklisted_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct ReceiveCooldownSet {
+        pub(crate) cooldown_blocks: u32,
+        pub(crate) exempt_mints_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct TransferFeeSet {
+        pub(crate) fee_bps: u16,
+        pub(crate) fee_recipient_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FeeExemptSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) exempt_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct TradingEnabled {
+        #[ink(topic)]
+        pub(crate) by_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MaxBalanceSet {
+        pub(crate) max_balance_opt: Option<Balance>,
+    }
+
+    #[ink(event)]
+    pub struct BalanceCapExemptSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) exempt_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct EraCapSet {
+        pub(crate) era_length_blocks: u32,
+        pub(crate) max_mint_per_era: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RecoveryThresholdSet {
+        pub(crate) recovery_threshold: u32,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipRecovered {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) recovery_nonce: u64,
+    }
+
+    #[ink(event)]
+    pub struct SpendLimitSet {
+        #[ink(topic)]
+        pub(crate) owner_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) spender_acc: AccountId,
+        pub(crate) total_limit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeeEventModeSet {
+        pub(crate) consolidated_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct BurnBpsSet {
+        pub(crate) burn_bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct MaxActiveAllowancesSet {
+        pub(crate) max_allowances_opt: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct CreatorFeeSet {
+        pub(crate) fee_bps: u16,
+        pub(crate) creator_treasury_opt: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct CreatorFeePaid {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) treasury_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ReversibleWindowSet {
+        pub(crate) window_blocks: u32,
+    }
+
+    #[ink(event)]
+    pub struct TransferReversed {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_val: Balance,
+        pub(crate) block_u32: u32,
+    }
+
     // constructors/messages/impls (formerly in logic.rs)
     impl Moo {
         // -------- constructors --------
 
         #[ink(constructor)]
         pub fn new() -> Self {
+            Self::new_with_minter(false)
+        }
+
+        /// Like `new`, minting `initial_supply` to `treasury_acc` at deploy time (a regular
+        /// `Minted` event) so the token never exists without supply. Zero mints nothing.
+        #[ink(constructor)]
+        pub fn new_with_treasury(initial_supply: Balance, treasury_acc: AccountId) -> Result<Self> {
+            let mut contract = Self::new_with_minter(false);
+            if initial_supply != 0 {
+                contract.mint_internal(treasury_acc, initial_supply)?;
+            }
+            Ok(contract)
+        }
+
+        /// Like `new`, with `decimals_u8` display decimals instead of the default 12. Fixed
+        /// for the contract's lifetime: there is deliberately no setter.
+        #[ink(constructor)]
+        pub fn new_with_decimals(decimals_u8: u8) -> Self {
+            let mut contract = Self::new_with_minter(false);
+            contract.decimals_u8 = decimals_u8;
+            contract
+        }
+
+        /// Like `new`, granting `ROLE_MINTER` to each of `minters_vec` (at most
+        /// `MAX_INITIAL_MINTERS`, else `Overflow`) with a `MinterSet` per account, instead
+        /// of a `set_minter` transaction each after deploy.
+        #[ink(constructor)]
+        pub fn new_with_minters(minters_vec: Vec<AccountId>) -> Result<Self> {
+            if minters_vec.len() > MAX_INITIAL_MINTERS {
+                return Err(Error::Overflow)
+            }
+            let mut contract = Self::new_with_minter(false);
+            for minter_acc in minters_vec {
+                contract.set_role(ROLE_MINTER, minter_acc, true)?;
+                Self::env().emit_event(MinterSet { minter_acc, enabled_flag: true });
+            }
+            Ok(contract)
+        }
+
+        /// Like `new`, optionally granting the deployer `ROLE_MINTER` in the same transaction.
+        /// Emits the genesis `OwnershipTransferred` (and `MinterSet`) so indexers see the
+        /// initial admin state in the event stream.
+        #[ink(constructor)]
+        pub fn new_with_minter(auto_minter_flag: bool) -> Self {
+            let owner_acc = Self::env().caller();
+            let mut roles = Mapping::default();
+            let mut role_cnt = Mapping::default();
+            roles.insert(&(ROLE_ADMIN, owner_acc), &true);
+            role_cnt.insert(&ROLE_ADMIN, &1u32);
+            if auto_minter_flag {
+                roles.insert(&(ROLE_MINTER, owner_acc), &true);
+                role_cnt.insert(&ROLE_MINTER, &1u32);
+            }
+            Self::env().emit_event(OwnershipTransferred { from_acc_opt: None, to_acc: owner_acc });
+            if auto_minter_flag {
+                Self::env().emit_event(MinterSet { minter_acc: owner_acc, enabled_flag: true });
+            }
             Self {
-                owner_acc: Self::env().caller(),
+                owner_acc,
                 paused_flag: false,
-                is_minter: Default::default(),
+                pause_reason: None,
+                roles,
+                role_cnt,
+                guardians: Default::default(),
+                decimals_u8: 12,
                 total_supply: 0,
                 balances: Default::default(),
                 allowances: Default::default(),
+                mint_nonces: Default::default(),
+                oracle_acc_opt: None,
+                staked: Default::default(),
+                total_staked: 0,
+                staker_cnt: 0,
+                reward_rate_per_block: 0,
+                reward_acc_val: 0,
+                reward_acc_block: Self::env().block_number(),
+                reward_debt: Default::default(),
+                reward_owed: Default::default(),
+                allow_self_transfer: false,
+                snapshot_id: 0,
+                bal_ckpt_cnt: Default::default(),
+                bal_ckpt: Default::default(),
+                dividends: Default::default(),
+                dividend_claimed: Default::default(),
+                dividend_pending: 0,
+                burn_to_dead_flag: false,
+                dead_acc: AccountId::from([0u8; 32]),
+                max_batch_size_u32: DEFAULT_MAX_BATCH_SIZE,
+                supply_at: Default::default(),
+                pause_nonces: Default::default(),
+                last_activity: Default::default(),
+                excluded: Default::default(),
+                excluded_list: Vec::new(),
+                min_transfer_amount: 0,
+                supply_floor: 0,
+                permit_nonces: Default::default(),
+                max_mint_per_call: 0,
+                blacklisted_spenders: Default::default(),
+                holder_cnt: 0,
+                holder_by_index: Default::default(),
+                holder_index: Default::default(),
+                receive_cooldown_blocks: 0,
+                cooldown_exempt_mints: true,
+                receive_locks: Default::default(),
+                transfer_fee_bps: 0,
+                fee_recipient_acc: owner_acc,
+                creator_treasury_opt: None,
+                creator_fee_bps: 0,
+                burn_bps_u16: 0,
+                consolidated_fee_event_flag: false,
+                fee_exempt: Default::default(),
+                trading_enabled: false,
+                max_balance_per_account: None,
+                balance_cap_exempt: Default::default(),
+                era_length_blocks: 0,
+                max_mint_per_era: 0,
+                era_index: 0,
+                era_minted: 0,
+                recovery_threshold: 0,
+                recovery_nonce: 0,
+                spend_limit: Default::default(),
+                operator_spent: Default::default(),
+                max_active_allowances: None,
+                active_allowance_cnt: Default::default(),
+                reversible_window_blocks: 0,
+                reversible: Default::default(),
+                reversible_gen: Default::default(),
                 storage_ver_u32: 1,
             }
         }
 
         // -------- modifiers (helpers) --------
 
+        /// Admin gate. The deployer (`owner_acc`) bootstraps as the first Admin.
         fn only_owner(&self) -> Result<()> {
-            if self.env().caller() != self.owner_acc {
+            if !self.has_role(ROLE_ADMIN, self.env().caller()) {
                 return Err(Error::NotOwner)
             }
             Ok(())
         }
 
+        fn only_role(&self, role_u8: u8) -> Result<()> {
+            if !self.has_role(role_u8, self.env().caller()) {
+                return Err(Error::Unauthorized)
+            }
+            Ok(())
+        }
+
         fn when_not_paused(&self) -> Result<()> {
             if self.paused_flag {
                 return Err(Error::Paused)
@@ -115,242 +829,3265 @@ mod moo {
             Ok(())
         }
 
+        fn when_spender_allowed(&self, spender_acc: AccountId) -> Result<()> {
+            if self.is_spender_blacklisted(spender_acc) {
+                return Err(Error::SpenderBlacklisted)
+            }
+            Ok(())
+        }
+
         // -------- admin / roles --------
 
         #[ink(message)]
         pub fn set_pause(&mut self, paused_flag: bool) -> Result<()> {
-            self.only_owner()?;
+            self.set_pause_with_reason(paused_flag, PAUSE_REASON_UNSPECIFIED)
+        }
+
+        /// Pause with a `PAUSE_REASON_*` code; the reason is cleared on unpause.
+        #[ink(message)]
+        pub fn set_pause_with_reason(&mut self, paused_flag: bool, reason_u8: u8) -> Result<()> {
+            if self.only_owner().is_err() {
+                self.only_role(ROLE_PAUSER)?;
+            }
             self.paused_flag = paused_flag;
+            self.pause_reason = if paused_flag { Some(reason_u8) } else { None };
             self.env().emit_event(PausedSet { paused_flag });
             Ok(())
         }
 
+        /// Why the token is paused, or `None` while it is live.
         #[ink(message)]
-        pub fn set_minter(&mut self, minter_acc: AccountId, enabled_flag: bool) -> Result<()> {
+        pub fn pause_reason(&self) -> Option<u8> {
+            self.pause_reason
+        }
+
+        #[ink(message)]
+        pub fn grant_role(&mut self, role_u8: u8, acc: AccountId) -> Result<()> {
             self.only_owner()?;
-            self.is_minter.insert(&minter_acc, &enabled_flag);
-            self.env().emit_event(MinterSet { minter_acc, enabled_flag });
-            Ok(())
+            self.set_role(role_u8, acc, true)
         }
 
-        // -------- read API --------
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role_u8: u8, acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(role_u8, acc, false)
+        }
 
+        /// `grant_role` for each account, one `RoleGranted` each. Atomic: an invalid role
+        /// fails the whole batch. Lists longer than `max_batch_size` are rejected with
+        /// `Overflow`.
         #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            self.total_supply
+        pub fn grant_role_batch(&mut self, role_u8: u8, accounts_vec: Vec<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            self.check_batch_len(accounts_vec.len())?;
+            for acc in accounts_vec {
+                self.set_role(role_u8, acc, true)?;
+            }
+            Ok(())
         }
 
+        /// `revoke_role` for each account; same rules as `grant_role_batch`.
         #[ink(message)]
-        pub fn balance_of(&self, owner_acc: AccountId) -> Balance {
-            self.balances.get(&owner_acc).unwrap_or(0)
+        pub fn revoke_role_batch(&mut self, role_u8: u8, accounts_vec: Vec<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            self.check_batch_len(accounts_vec.len())?;
+            for acc in accounts_vec {
+                self.set_role(role_u8, acc, false)?;
+            }
+            Ok(())
         }
 
         #[ink(message)]
-        pub fn my_balance(&self) -> Balance {
-            let caller_acc = self.env().caller();
-            self.balance_of(caller_acc)
+        pub fn has_role(&self, role_u8: u8, acc: AccountId) -> bool {
+            self.roles.get(&(role_u8, acc)).unwrap_or(false)
         }
 
+        /// Every role `acc` holds in one read: bit `1 << ROLE_*` is set per role, i.e.
+        /// 0x1 admin, 0x2 minter, 0x4 pauser, 0x8 burner. Higher bits are always clear.
         #[ink(message)]
-        pub fn allowance(&self, owner_acc: AccountId, spender_acc: AccountId) -> Balance {
-            self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0)
+        pub fn roles_of(&self, acc: AccountId) -> u32 {
+            [ROLE_ADMIN, ROLE_MINTER, ROLE_PAUSER, ROLE_BURNER]
+                .into_iter()
+                .filter(|role_u8| self.has_role(*role_u8, acc))
+                .fold(0, |mask_u32, role_u8| mask_u32 | (1 << role_u8))
         }
 
-        // -------- write API --------
+        /// Shorthand for granting/revoking `ROLE_MINTER`; still emits `MinterSet`.
+        #[ink(message)]
+        pub fn set_minter(&mut self, minter_acc: AccountId, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(ROLE_MINTER, minter_acc, enabled_flag)?;
+            self.env().emit_event(MinterSet { minter_acc, enabled_flag });
+            Ok(())
+        }
 
-        /// Privileged mint: caller must be marked as a minter.
         #[ink(message)]
-        pub fn mint(&mut self, amount_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            if amount_val == 0 {
-                return Err(Error::AmountZero)
+        pub fn set_guardian(&mut self, guardian_acc: AccountId, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.guardians.insert(&guardian_acc, &enabled_flag);
+            self.env().emit_event(GuardianSet { guardian_acc, enabled_flag });
+            Ok(())
+        }
+
+        /// Stop `spender_acc` from spending any owner's allowance (`transfer_from` and
+        /// `burn_from`) without touching the allowances themselves.
+        #[ink(message)]
+        pub fn blacklist_spender(&mut self, spender_acc: AccountId, blacklisted_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            if blacklisted_flag {
+                self.blacklisted_spenders.insert(&spender_acc, &true);
+            } else {
+                self.blacklisted_spenders.remove(&spender_acc);
             }
-            let caller_acc = self.env().caller();
-            let allowed_flag = self.is_minter.get(&caller_acc).unwrap_or(false);
-            if !allowed_flag {
+            self.env().emit_event(SpenderBlacklisted { spender_acc, blacklisted_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_spender_blacklisted(&self, acc: AccountId) -> bool {
+            self.blacklisted_spenders.get(&acc).unwrap_or(false)
+        }
+
+        /// Guardians can stop the token but never restart it; unpausing stays with the owner.
+        #[ink(message)]
+        pub fn emergency_pause(&mut self) -> Result<()> {
+            let by_acc = self.env().caller();
+            if !self.is_guardian(by_acc) {
                 return Err(Error::Unauthorized)
             }
-            self.mint_internal(caller_acc, amount_val)
+            self.emergency_stop(by_acc);
+            Ok(())
         }
 
+        /// Relayed emergency pause: anyone may submit, but `signature_val` must be an ECDSA
+        /// signature by an admin or guardian over `(contract, b"pause", nonce_val)`.
+        /// Unpausing stays a direct owner call.
         #[ink(message)]
-        pub fn burn(&mut self, amount_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            if amount_val == 0 {
-                return Err(Error::AmountZero)
+        pub fn pause_with_sig(&mut self, signature_val: [u8; 65], nonce_val: u64) -> Result<()> {
+            let msg_hash = self
+                .env()
+                .hash_encoded::<Blake2x256, _>(&(self.env().account_id(), b"pause", nonce_val));
+            let signer_acc = self.recover_signer(&msg_hash, &signature_val)?;
+            if !self.has_role(ROLE_ADMIN, signer_acc) && !self.is_guardian(signer_acc) {
+                return Err(Error::UnauthorizedSigner)
             }
-            let from_acc = self.env().caller();
-            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
-            if from_bal < amount_val {
-                return Err(Error::InsufficientBalance)
+            if self.pause_nonces.get(&(signer_acc, nonce_val)).unwrap_or(false) {
+                return Err(Error::NonceUsed)
             }
-            let new_from_bal = from_bal.checked_sub(amount_val).ok_or(Error::Overflow)?;
-            self.balances.insert(&from_acc, &new_from_bal);
-            self.total_supply = self.total_supply.checked_sub(amount_val).ok_or(Error::Overflow)?;
-            self.env().emit_event(Burned { from_acc, amount_val });
+            self.pause_nonces.insert(&(signer_acc, nonce_val), &true);
+            self.emergency_stop(signer_acc);
             Ok(())
         }
 
         #[ink(message)]
-        pub fn transfer(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            if amount_val == 0 {
-                return Err(Error::AmountZero)
+        pub fn is_pause_nonce_used(&self, signer_acc: AccountId, nonce_val: u64) -> bool {
+            self.pause_nonces.get(&(signer_acc, nonce_val)).unwrap_or(false)
+        }
+
+        /// How many accounts currently hold `role_u8`.
+        #[ink(message)]
+        pub fn role_count(&self, role_u8: u8) -> u32 {
+            self.role_cnt.get(&role_u8).unwrap_or(0)
+        }
+
+        /// Owner, role head-counts, pause state and mint/balance caps in one read.
+        #[ink(message)]
+        pub fn role_config(&self) -> RoleConfig {
+            RoleConfig {
+                schema_ver: self.storage_ver_u32,
+                owner: self.owner_acc,
+                admin_cnt: self.role_count(ROLE_ADMIN),
+                minter_cnt: self.role_count(ROLE_MINTER),
+                pauser_cnt: self.role_count(ROLE_PAUSER),
+                burner_cnt: self.role_count(ROLE_BURNER),
+                paused: self.paused_flag,
+                oracle: self.oracle_acc_opt,
+                max_mint_per_call: self.max_mint_per_call,
+                era_length_blocks: self.era_length_blocks,
+                max_mint_per_era: self.max_mint_per_era,
+                max_balance_per_account: self.max_balance_per_account,
+                supply_floor: self.supply_floor,
+                renounced: self.is_renounced(),
             }
+        }
+
+        /// Give up ownership for good: the last admin drops its role, `owner_acc` becomes
+        /// the zero address and guardian recovery is switched off, so the configuration is
+        /// frozen. Only `owner_acc` may call it, and only once every other admin has been
+        /// revoked (`OtherAdmins`). Emits `OwnershipTransferred` to the zero address.
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<()> {
             let from_acc = self.env().caller();
-            if from_acc == to_acc {
-                return Err(Error::SameAccount)
+            if from_acc != self.owner_acc || !self.has_role(ROLE_ADMIN, from_acc) {
+                return Err(Error::NotOwner)
             }
-            self.move_balance(from_acc, to_acc, amount_val)
+            if self.role_count(ROLE_ADMIN) > 1 {
+                return Err(Error::OtherAdmins)
+            }
+            self.set_role(ROLE_ADMIN, from_acc, false)?;
+            let zero_acc = AccountId::from([0u8; 32]);
+            self.owner_acc = zero_acc;
+            self.recovery_threshold = 0;
+            self.env().emit_event(OwnershipTransferred { from_acc_opt: Some(from_acc), to_acc: zero_acc });
+            Ok(())
         }
 
+        /// `true` once `renounce_ownership` ran: the owner is the zero address and no
+        /// account holds `ROLE_ADMIN`, so the contract's settings are immutable.
         #[ink(message)]
-        pub fn approve(&mut self, spender_acc: AccountId, amount_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            let owner_acc = self.env().caller();
-            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
-            // Safe-approve: forbid nonzero -> nonzero without zeroing first
-            if current_val != 0 && amount_val != 0 {
-                return Err(Error::AllowanceRace)
-            }
-            self.allowances.insert(&(owner_acc, spender_acc), &amount_val);
-            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val });
+        pub fn is_renounced(&self) -> bool {
+            self.owner_acc == AccountId::from([0u8; 32]) && self.role_count(ROLE_ADMIN) == 0
+        }
+
+        #[ink(message)]
+        pub fn is_guardian(&self, acc: AccountId) -> bool {
+            self.guardians.get(&acc).unwrap_or(false)
+        }
+
+        /// How many distinct guardian signatures `recover_ownership` needs. Must be set
+        /// while the owner key is still available; 0 disables recovery.
+        #[ink(message)]
+        pub fn set_recovery_threshold(&mut self, recovery_threshold: u32) -> Result<()> {
+            self.only_owner()?;
+            self.recovery_threshold = recovery_threshold;
+            self.env().emit_event(RecoveryThresholdSet { recovery_threshold });
             Ok(())
         }
 
+        /// `(recovery_threshold, recovery_nonce)`; guardians sign the current nonce.
         #[ink(message)]
-        pub fn increase_allowance(&mut self, spender_acc: AccountId, add_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            let owner_acc = self.env().caller();
-            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
-            let new_val = current_val.checked_add(add_val).ok_or(Error::Overflow)?;
-            self.allowances.insert(&(owner_acc, spender_acc), &new_val);
-            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val: new_val });
+        pub fn recovery_config(&self) -> (u32, u64) {
+            (self.recovery_threshold, self.recovery_nonce)
+        }
+
+        /// Key-loss recovery: anyone may submit, but at least `recovery_threshold` distinct
+        /// guardians must have ECDSA-signed `(contract, b"recover", new_owner_acc,
+        /// recovery_nonce)`. Moves the admin role from `owner_acc` to `new_owner_acc` and
+        /// bumps the nonce so the signatures can't be replayed. Signatures from non-guardians
+        /// fail with `UnauthorizedSigner`; repeated signers count once.
+        #[ink(message)]
+        pub fn recover_ownership(&mut self, new_owner_acc: AccountId, signatures_vec: Vec<[u8; 65]>) -> Result<()> {
+            if self.recovery_threshold == 0 {
+                return Err(Error::RecoveryNotConfigured)
+            }
+            if signatures_vec.len() > MAX_RECOVERY_SIGS {
+                return Err(Error::Overflow)
+            }
+            let msg_hash = self.env().hash_encoded::<Blake2x256, _>(&(
+                self.env().account_id(),
+                b"recover",
+                new_owner_acc,
+                self.recovery_nonce,
+            ));
+            let mut signers_vec: Vec<AccountId> = Vec::new();
+            for signature_val in signatures_vec.iter() {
+                let signer_acc = self.recover_signer(&msg_hash, signature_val)?;
+                if !self.is_guardian(signer_acc) {
+                    return Err(Error::UnauthorizedSigner)
+                }
+                if !signers_vec.contains(&signer_acc) {
+                    signers_vec.push(signer_acc);
+                }
+            }
+            if (signers_vec.len() as u32) < self.recovery_threshold {
+                return Err(Error::RecoveryThreshold)
+            }
+            let from_acc = self.owner_acc;
+            self.set_role(ROLE_ADMIN, from_acc, false)?;
+            self.set_role(ROLE_ADMIN, new_owner_acc, true)?;
+            self.owner_acc = new_owner_acc;
+            let recovery_nonce = self.recovery_nonce;
+            self.recovery_nonce = recovery_nonce.checked_add(1).ok_or(Error::Overflow)?;
+            self.env().emit_event(OwnershipRecovered { from_acc, to_acc: new_owner_acc, recovery_nonce });
             Ok(())
         }
 
+        /// Point minting at an oracle exposing `max_mintable() -> Balance`; `None` disables it.
         #[ink(message)]
-        pub fn decrease_allowance(&mut self, spender_acc: AccountId, sub_val: Balance) -> Result<()> {
-            self.when_not_paused()?;
-            let owner_acc = self.env().caller();
-            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
-            let new_val = current_val.saturating_sub(sub_val);
-            self.allowances.insert(&(owner_acc, spender_acc), &new_val);
-            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val: new_val });
+        pub fn set_oracle(&mut self, oracle_acc_opt: Option<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            self.oracle_acc_opt = oracle_acc_opt;
+            self.env().emit_event(OracleSet { oracle_acc_opt });
             Ok(())
         }
 
         #[ink(message)]
-        pub fn transfer_from(
-            &mut self,
-            from_acc: AccountId,
-            to_acc: AccountId,
-            amount_val: Balance,
-        ) -> Result<()> {
-            self.when_not_paused()?;
+        pub fn oracle(&self) -> Option<AccountId> {
+            self.oracle_acc_opt
+        }
+
+        /// Send tokens stuck at this contract's address to `to_acc`. Moo held by the
+        /// contract itself is moved internally; any other token is pulled via PSP22.
+        #[ink(message)]
+        pub fn rescue_token(&mut self, token_acc: AccountId, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.only_owner()?;
             if amount_val == 0 {
                 return Err(Error::AmountZero)
             }
-            if from_acc == to_acc {
+            let self_acc = self.env().account_id();
+            if to_acc == self_acc {
                 return Err(Error::SameAccount)
             }
-
-            // Precheck balances to avoid burning allowance on failure
-            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
-            if from_bal < amount_val {
-                return Err(Error::InsufficientBalance)
+            if token_acc == self_acc {
+                // only the contract's own free balance; staked funds are never rescuable
+                let free_bal = self.balance_of(self_acc).saturating_sub(self.total_staked);
+                if free_bal < amount_val {
+                    return Err(Error::InsufficientBalance)
+                }
+                self.move_balance(self_acc, to_acc, amount_val)?;
+            } else {
+                let mut token_ref: PSP22Ref = token_acc.into();
+                let call_res = token_ref
+                    .call_mut()
+                    .transfer(to_acc, amount_val, Vec::new())
+                    .try_invoke();
+                if !matches!(call_res, Ok(Ok(Ok(())))) {
+                    return Err(Error::RescueFailed)
+                }
             }
+            self.env().emit_event(TokenRescued { token_acc, to_acc, amount_val });
+            Ok(())
+        }
 
-            // Check allowance
-            let caller_acc = self.env().caller();
-            let current_allow = self.allowances.get(&(from_acc, caller_acc)).unwrap_or(0);
-            if current_allow < amount_val {
-                return Err(Error::InsufficientAllowance)
+        // -------- read API --------
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// The contract's free native balance minus the existential deposit and unclaimed
+        /// dividend pools. Transfer fees are paid in Moo, so they never touch it.
+        #[ink(message)]
+        pub fn native_reserve(&self) -> Balance {
+            self.env()
+                .balance()
+                .saturating_sub(self.env().minimum_balance())
+                .saturating_sub(self.dividend_pending)
+        }
+
+        /// Raw comparison `native_reserve() >= total_supply`, read on-chain. Moo has no
+        /// deposit/withdraw wrapper: supply is minted by role holders, not against native
+        /// deposits, so this says nothing about a peg unless the operator funds the
+        /// contract one-to-one. It is only a proof of reserve once such a wrapper exists.
+        #[ink(message)]
+        pub fn is_fully_backed(&self) -> bool {
+            self.native_reserve() >= self.total_supply
+        }
+
+        /// `native_reserve / total_supply` in bps, saturating at `u32::MAX`; 10000 while
+        /// nothing is minted. The same raw ratio as `is_fully_backed`, with the same caveat.
+        #[ink(message)]
+        pub fn reserve_ratio(&self) -> u32 {
+            if self.total_supply == 0 {
+                return 10_000
             }
+            let ratio_val = self.native_reserve().saturating_mul(10_000) / self.total_supply;
+            u32::try_from(ratio_val).unwrap_or(u32::MAX)
+        }
 
-            // Move balances (overflow-safe)
-            let to_bal = self.balances.get(&to_acc).unwrap_or(0);
-            let new_from = from_bal.checked_sub(amount_val).ok_or(Error::Overflow)?;
-            let new_to = to_bal.checked_add(amount_val).ok_or(Error::Overflow)?;
-            self.balances.insert(&from_acc, &new_from);
-            self.balances.insert(&to_acc, &new_to);
-            self.env().emit_event(Transferred { from_acc, to_acc, amount_val });
+        /// `total_supply` minus the balances of the owner and every `set_excluded` account
+        /// (treasury, team wallets, ...).
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> Balance {
+            let mut held_val = self.balance_of(self.owner_acc);
+            for acc in self.excluded_list.iter() {
+                if *acc != self.owner_acc {
+                    held_val = held_val.saturating_add(self.balance_of(*acc));
+                }
+            }
+            self.total_supply.saturating_sub(held_val)
+        }
 
-            // Reduce allowance last
-            let new_allow = current_allow - amount_val;
-            self.allowances.insert(&(from_acc, caller_acc), &new_allow);
+        /// Add or remove an account from the circulating-supply exclusions (at most
+        /// `MAX_EXCLUDED`).
+        #[ink(message)]
+        pub fn set_excluded(&mut self, acc: AccountId, excluded_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            let was_excluded = self.excluded.get(&acc).unwrap_or(false);
+            if excluded_flag && !was_excluded {
+                if self.excluded_list.len() >= MAX_EXCLUDED {
+                    return Err(Error::Overflow)
+                }
+                self.excluded.insert(&acc, &true);
+                self.excluded_list.push(acc);
+            } else if !excluded_flag && was_excluded {
+                self.excluded.remove(&acc);
+                self.excluded_list.retain(|listed_acc| *listed_acc != acc);
+            }
+            self.env().emit_event(ExcludedSet { acc, excluded_flag });
             Ok(())
         }
 
-        // ---- internals ----
+        /// Accounts excluded via `set_excluded`; the owner is always excluded as well.
+        #[ink(message)]
+        pub fn excluded_accounts(&self) -> Vec<AccountId> {
+            self.excluded_list.clone()
+        }
 
-        fn mint_internal(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
-            let new_total = self.total_supply.checked_add(amount_val).ok_or(Error::Overflow)?;
-            self.total_supply = new_total;
+        #[ink(message)]
+        pub fn balance_of(&self, owner_acc: AccountId) -> Balance {
+            self.balances.get(&owner_acc).unwrap_or(0)
+        }
 
-            let to_bal = self.balances.get(&to_acc).unwrap_or(0);
-            let new_to = to_bal.checked_add(amount_val).ok_or(Error::Overflow)?;
-            self.balances.insert(&to_acc, &new_to);
+        /// Number of accounts with a nonzero balance.
+        #[ink(message)]
+        pub fn holder_count(&self) -> u32 {
+            self.holder_cnt
+        }
 
-            self.env().emit_event(Minted { to_acc, amount_val });
-            Ok(())
+        /// Paginated holder list (order not stable as holders leave).
+        #[ink(message)]
+        pub fn holders(&self, start_index: u32, limit_cnt: u32) -> Vec<AccountId> {
+            let end_index = core::cmp::min(self.holder_cnt, start_index.saturating_add(limit_cnt));
+            (start_index..end_index)
+                .filter_map(|index_val| self.holder_by_index.get(&index_val))
+                .collect()
         }
 
-        fn move_balance(&mut self, from_acc: AccountId, to_acc: AccountId, amount_val: Balance) -> Result<()> {
-            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
-            if from_bal < amount_val {
-                return Err(Error::InsufficientBalance)
-            }
-            let new_from = from_bal.checked_sub(amount_val).ok_or(Error::Overflow)?;
-            self.balances.insert(&from_acc, &new_from);
+        #[ink(message)]
+        pub fn my_balance(&self) -> Balance {
+            let caller_acc = self.env().caller();
+            self.balance_of(caller_acc)
+        }
 
-            let to_bal = self.balances.get(&to_acc).unwrap_or(0);
-            let new_to = to_bal.checked_add(amount_val).ok_or(Error::Overflow)?;
-            self.balances.insert(&to_acc, &new_to);
+        #[ink(message)]
+        pub fn allowance(&self, owner_acc: AccountId, spender_acc: AccountId) -> Balance {
+            self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0)
+        }
 
-            self.env().emit_event(Transferred { from_acc, to_acc, amount_val });
-            Ok(())
+        /// Timestamp (ms) of the last transfer, mint or burn touching `acc`; 0 if never.
+        #[ink(message)]
+        pub fn last_activity_of(&self, acc: AccountId) -> u64 {
+            self.last_activity.get(&acc).unwrap_or(0)
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        /// `(balance_of(owner), allowance(owner, spender))` read in one call, so approval
+        /// screens see a consistent pair.
+        #[ink(message)]
+        pub fn balance_and_allowance(&self, owner_acc: AccountId, spender_acc: AccountId) -> (Balance, Balance) {
+            (self.balance_of(owner_acc), self.allowance(owner_acc, spender_acc))
+        }
 
-        #[ink::test]
-        fn mint_and_transfer_works() {
-            let mut c = Moo::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            assert_eq!(c.total_supply(), 0);
-            assert!(c.set_minter(accounts.bob, true).is_ok());
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            assert!(c.mint(100).is_ok());
-            assert_eq!(c.total_supply(), 100);
-            assert_eq!(c.balance_of(accounts.bob), 100);
-            assert!(c.transfer(accounts.charlie, 40).is_ok());
-            assert_eq!(c.balance_of(accounts.bob), 60);
-            assert_eq!(c.balance_of(accounts.charlie), 40);
+        /// How much more allowance `spender_acc` needs from `owner_acc` to pull
+        /// `needed_val` (0 if the current allowance already covers it).
+        #[ink(message)]
+        pub fn allowance_shortfall(&self, owner_acc: AccountId, spender_acc: AccountId, needed_val: Balance) -> Balance {
+            needed_val.saturating_sub(self.allowance(owner_acc, spender_acc))
         }
 
-        #[ink::test]
-        fn pause_blocks_actions() {
-            let mut c = Moo::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            assert!(c.set_pause(true).is_ok());
-            assert!(c.set_minter(accounts.bob, true).is_ok());
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            assert!(matches!(c.mint(1), Err(Error::Paused)));
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals_u8
         }
 
-        #[ink::test]
-        fn set_minter_requires_owner() {
-            let mut c = Moo::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            assert_eq!(c.set_minter(accounts.charlie, true), Err(Error::NotOwner));
+        /// Split `amount_val` into `(whole, fraction)` at `decimals_u8`, e.g. 1.5 units with
+        /// 12 decimals is `(1, 500_000_000_000)`. Integer-only, so frontends avoid float math.
+        #[ink(message)]
+        pub fn to_display(&self, amount_val: Balance) -> (u128, u128) {
+            match 10u128.checked_pow(u32::from(self.decimals_u8)) {
+                Some(unit_val) => (amount_val / unit_val, amount_val % unit_val),
+                None => (0, amount_val),
+            }
+        }
+
+        /// Message for an encoded `Error` discriminant, for tooling that only sees the
+        /// raw code; unknown codes map to "unknown error".
+        #[ink(message)]
+        pub fn error_message(&self, code_u8: u8) -> String {
+            match Error::decode(&mut &[code_u8][..]) {
+                Ok(err) => String::from(err.message()),
+                Err(_) => String::from("unknown error"),
+            }
+        }
+
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            self.storage_ver_u32
+        }
+
+        /// Bitmask of `CAP_*` features this instance supports. Bits for features that
+        /// are not built into this version (metadata, supply cap, vesting) stay 0;
+        /// `CAP_ORACLE` is only set while an oracle is configured.
+        #[ink(message)]
+        pub fn capabilities(&self) -> u32 {
+            let mut caps_u32 =
+                CAP_PERMIT | CAP_MINT_WITH_SIG | CAP_GUARDIANS | CAP_RESCUE | CAP_ROLES | CAP_STAKING;
+            if self.oracle_acc_opt.is_some() {
+                caps_u32 |= CAP_ORACLE;
+            }
+            caps_u32
+        }
+
+        /// Selectors of every message this build dispatches, taken from ink!'s generated
+        /// dispatch table so the list can't drift from the real message set. Lets a generic
+        /// client check compatibility without the metadata file.
+        #[ink(message)]
+        pub fn message_selectors(&self) -> Vec<[u8; 4]> {
+            <Moo as ink::reflect::ContractDispatchableMessages<
+                { <Moo as ink::reflect::ContractAmountDispatchables>::MESSAGES },
+            >>::IDS
+                .iter()
+                .map(|id_u32| id_u32.to_be_bytes())
+                .collect()
+        }
+
+        /// Whether `selector_val` is one of `message_selectors`.
+        #[ink(message)]
+        pub fn supports_selector(&self, selector_val: [u8; 4]) -> bool {
+            self.message_selectors().contains(&selector_val)
+        }
+
+        /// Route burns to `dead_acc` (a plain `Transferred` into a visible burn address,
+        /// `total_supply` unchanged) instead of destroying supply (`Burned`, the default).
+        /// A routed burn moves the full amount: `dead_acc` is fee-exempt while routing, so
+        /// no transfer fee, burn share or creator fee is taken on the way.
+        #[ink(message)]
+        pub fn set_burn_mode(&mut self, burn_to_dead_flag: bool, dead_acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            self.burn_to_dead_flag = burn_to_dead_flag;
+            self.dead_acc = dead_acc;
+            self.env().emit_event(BurnModeSet { burn_to_dead_flag, dead_acc });
+            Ok(())
+        }
+
+        /// `(burn_to_dead_flag, dead_acc)`.
+        #[ink(message)]
+        pub fn burn_mode(&self) -> (bool, AccountId) {
+            (self.burn_to_dead_flag, self.dead_acc)
+        }
+
+        /// Lowest `total_supply` that `burn`/`burn_from` may leave behind. Burns routed to
+        /// the dead address don't reduce supply and are unaffected.
+        #[ink(message)]
+        pub fn set_supply_floor(&mut self, supply_floor: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.supply_floor = supply_floor;
+            self.env().emit_event(SupplyFloorSet { supply_floor });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn supply_floor(&self) -> Balance {
+            self.supply_floor
+        }
+
+        // -------- write API --------
+
+        /// Privileged mint: caller must be marked as a minter.
+        #[ink(message)]
+        pub fn mint(&mut self, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.only_role(ROLE_MINTER)?;
+            self.check_mint_per_call(amount_val)?;
+            self.spend_era_budget(amount_val)?;
+            let caller_acc = self.env().caller();
+            self.mint_internal(caller_acc, amount_val)
+        }
+
+        /// `mint` to `to_acc` instead of the caller (same role, per-call and era checks),
+        /// e.g. for a game contract paying rewards straight to players.
+        #[ink(message)]
+        pub fn mint_to(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.only_role(ROLE_MINTER)?;
+            self.check_mint_per_call(amount_val)?;
+            self.spend_era_budget(amount_val)?;
+            self.mint_internal(to_acc, amount_val)
+        }
+
+        /// Cap each `mint`/`mint_with_sig` at `max_mint_per_call` base units to bound what a
+        /// compromised minter can do per call. Zero means unlimited. Reward mints are exempt.
+        #[ink(message)]
+        pub fn set_max_mint_per_call(&mut self, max_mint_per_call: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.max_mint_per_call = max_mint_per_call;
+            self.env().emit_event(MaxMintPerCallSet { max_mint_per_call });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_mint_per_call(&self) -> Balance {
+            self.max_mint_per_call
+        }
+
+        /// Bounded inflation without a hard total cap: `mint`, `mint_to`, `mint_with_sig`
+        /// and staking rewards (`claim_rewards`) may create at most `max_mint_per_era` per
+        /// era of `era_length_blocks` blocks (era = `block_number / era_length_blocks`).
+        /// Zero blocks disables the cap. Reconfiguring carries what the current era has
+        /// already minted over to the new budget; only enabling the cap starts from zero.
+        #[ink(message)]
+        pub fn set_era_cap(&mut self, era_length_blocks: u32, max_mint_per_era: Balance) -> Result<()> {
+            self.only_owner()?;
+            // nothing was counted while the cap was off, and a finished era has no carry-over
+            if self.era_length_blocks == 0 || self.era_index != self.current_era() {
+                self.era_minted = 0;
+            }
+            self.era_length_blocks = era_length_blocks;
+            self.max_mint_per_era = max_mint_per_era;
+            self.era_index = self.current_era();
+            self.env().emit_event(EraCapSet { era_length_blocks, max_mint_per_era });
+            Ok(())
+        }
+
+        /// Current era number (0 while the era cap is off).
+        #[ink(message)]
+        pub fn current_era(&self) -> u32 {
+            self.env().block_number().checked_div(self.era_length_blocks).unwrap_or(0)
+        }
+
+        /// Mintable amount left in the current era (`Balance::MAX` while the cap is off).
+        #[ink(message)]
+        pub fn era_remaining(&self) -> Balance {
+            if self.era_length_blocks == 0 {
+                return Balance::MAX
+            }
+            if self.era_index != self.current_era() {
+                return self.max_mint_per_era
+            }
+            self.max_mint_per_era.saturating_sub(self.era_minted)
+        }
+
+        /// Relayed mint: anyone may submit, but `signature_val` must be an ECDSA
+        /// signature by a minter over `(contract, to_acc, amount_val, nonce_val)`.
+        #[ink(message)]
+        pub fn mint_with_sig(
+            &mut self,
+            to_acc: AccountId,
+            amount_val: Balance,
+            nonce_val: u64,
+            signature_val: [u8; 65],
+        ) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.check_mint_per_call(amount_val)?;
+            let msg_hash = self
+                .env()
+                .hash_encoded::<Blake2x256, _>(&(self.env().account_id(), to_acc, amount_val, nonce_val));
+            let signer_acc = self.recover_signer(&msg_hash, &signature_val)?;
+            if !self.has_role(ROLE_MINTER, signer_acc) {
+                return Err(Error::UnauthorizedSigner)
+            }
+            if self.mint_nonces.get(&(signer_acc, nonce_val)).unwrap_or(false) {
+                return Err(Error::NonceUsed)
+            }
+            self.mint_nonces.insert(&(signer_acc, nonce_val), &true);
+            self.spend_era_budget(amount_val)?;
+            self.mint_internal(to_acc, amount_val)
+        }
+
+        #[ink(message)]
+        pub fn is_mint_nonce_used(&self, signer_acc: AccountId, nonce_val: u64) -> bool {
+            self.mint_nonces.get(&(signer_acc, nonce_val)).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn burn(&mut self, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let from_acc = self.env().caller();
+            self.burn_internal(from_acc, amount_val)
+        }
+
+        /// Burn tagged with an order reference for off-chain redemption. Emits the usual
+        /// `Burned` plus `BurnedWithMemo`; memos longer than `MAX_MEMO_LEN` are rejected.
+        #[ink(message)]
+        pub fn burn_with_memo(&mut self, amount_val: Balance, memo_vec: Vec<u8>) -> Result<()> {
+            if memo_vec.len() > MAX_MEMO_LEN {
+                return Err(Error::MemoTooLong)
+            }
+            self.burn(amount_val)?;
+            let from_acc = self.env().caller();
+            self.env().emit_event(BurnedWithMemo { from_acc, amount_val, memo_vec });
+            Ok(())
+        }
+
+        /// Burner-role burn of someone else's tokens; spends the caller's allowance and
+        /// counts against any `approve_with_limit` cap, like `transfer_from`.
+        #[ink(message)]
+        pub fn burn_from(&mut self, from_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            self.only_role(ROLE_BURNER)?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let caller_acc = self.env().caller();
+            self.when_spender_allowed(caller_acc)?;
+            let current_allow = self.allowances.get(&(from_acc, caller_acc)).unwrap_or(0);
+            if current_allow < amount_val {
+                return Err(Error::InsufficientAllowance)
+            }
+            if self.balance_of(from_acc) < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+            self.spend_within_limit(from_acc, caller_acc, amount_val)?;
+            self.burn_internal(from_acc, amount_val)?;
+            let new_allow = current_allow - amount_val;
+            self.write_allowance(from_acc, caller_acc, new_allow)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.check_min_transfer(amount_val)?;
+            let from_acc = self.env().caller();
+            self.when_trading(from_acc)?;
+            self.when_cooled_down(from_acc, amount_val)?;
+            if from_acc == to_acc {
+                return self.self_transfer(from_acc, amount_val)
+            }
+            self.move_balance(from_acc, to_acc, amount_val)
+        }
+
+        /// Enable clawbacks: a `transfer_reversible` can be undone by its sender for
+        /// `window_blocks` blocks. Zero (the default) disables the feature. Accounts that
+        /// have received a reversible transfer pay one extra storage read on every debit.
+        #[ink(message)]
+        pub fn set_reversible_window(&mut self, window_blocks: u32) -> Result<()> {
+            self.only_owner()?;
+            self.reversible_window_blocks = window_blocks;
+            self.env().emit_event(ReversibleWindowSet { window_blocks });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn reversible_window(&self) -> u32 {
+            self.reversible_window_blocks
+        }
+
+        /// `transfer` that the caller may undo with `reverse_transfer(to_acc, block)` within
+        /// the window. Records what `to_acc` actually received (after fees and burns) under
+        /// the current block. `NotReversible` while the feature is off.
+        #[ink(message)]
+        pub fn transfer_reversible(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            if self.reversible_window_blocks == 0 {
+                return Err(Error::NotReversible)
+            }
+            let from_acc = self.env().caller();
+            if from_acc == to_acc {
+                return Err(Error::SameAccount)
+            }
+            let before_bal = self.balance_of(to_acc);
+            self.transfer(to_acc, amount_val)?;
+            let received_val = self.balance_of(to_acc).saturating_sub(before_bal);
+            let (gen_u32, _) = self.reversible_gen.get(&to_acc).unwrap_or((0, false));
+            self.reversible_gen.insert(&to_acc, &(gen_u32, true));
+            let key = (from_acc, to_acc, self.env().block_number());
+            let recorded_val = self.reversible_of(from_acc, to_acc, key.2);
+            self.reversible
+                .insert(&key, &(recorded_val.checked_add(received_val).ok_or(Error::Overflow)?, gen_u32));
+            Ok(())
+        }
+
+        /// Sender-only: pull back what a `transfer_reversible` to `to_acc` in `block_u32`
+        /// delivered while the window is open. Received funds count as spent first: any
+        /// debit from `to_acc` after the receipt voids all its open receipts, so a clawback
+        /// never reaches the recipient's other money (`NotReversible` then, or once the
+        /// window closes). The funds move back like a transfer from `to_acc`: pause, the
+        /// caller's spender blacklist and the trading gate apply, and so do fees.
+        #[ink(message)]
+        pub fn reverse_transfer(&mut self, to_acc: AccountId, block_u32: u32) -> Result<()> {
+            self.when_not_paused()?;
+            let from_acc = self.env().caller();
+            self.when_spender_allowed(from_acc)?;
+            let amount_val = self.reversible_of(from_acc, to_acc, block_u32);
+            if amount_val == 0
+                || self.env().block_number().saturating_sub(block_u32) > self.reversible_window_blocks
+            {
+                return Err(Error::NotReversible)
+            }
+            self.when_trading(to_acc)?;
+            self.reversible.remove(&(from_acc, to_acc, block_u32));
+            let gen_opt = self.reversible_gen.get(&to_acc);
+            self.move_balance(to_acc, from_acc, amount_val)?;
+            // the clawback itself isn't the recipient spending: other receipts stay reversible
+            if let Some(gen_val) = gen_opt {
+                self.reversible_gen.insert(&to_acc, &gen_val);
+            }
+            self.env().emit_event(TransferReversed { from_acc, to_acc, amount_val, block_u32 });
+            Ok(())
+        }
+
+        /// Amount of `from_acc`'s reversible transfers to `to_acc` recorded in `block_u32`
+        /// and not voided by `to_acc` spending since.
+        #[ink(message)]
+        pub fn reversible_of(&self, from_acc: AccountId, to_acc: AccountId, block_u32: u32) -> Balance {
+            let (gen_u32, _) = self.reversible_gen.get(&to_acc).unwrap_or((0, false));
+            match self.reversible.get(&(from_acc, to_acc, block_u32)) {
+                Some((amount_val, record_gen)) if record_gen == gen_u32 => amount_val,
+                _ => 0,
+            }
+        }
+
+        /// `transfer`, then, if `to_acc` is a contract, call its
+        /// `on_received(from, amount, data)` with `data_vec` forwarded verbatim (at most
+        /// `MAX_CALL_DATA_LEN` bytes). `amount` is what `to_acc` actually received, i.e.
+        /// `amount_val` net of the transfer fee, burn and creator fee. The receiver must
+        /// return `Ok(())` (as `Result<(), u8>`); anything else reverts the transfer.
+        #[ink(message)]
+        pub fn transfer_and_call(&mut self, to_acc: AccountId, amount_val: Balance, data_vec: Vec<u8>) -> Result<()> {
+            if data_vec.len() > MAX_CALL_DATA_LEN {
+                return Err(Error::CallDataTooLong)
+            }
+            let before_val = self.balance_of(to_acc);
+            self.transfer(to_acc, amount_val)?;
+            if !self.env().is_contract(&to_acc) {
+                return Ok(())
+            }
+            let received_val = self.balance_of(to_acc).saturating_sub(before_val);
+            let from_acc = self.env().caller();
+            let call_res = build_call::<DefaultEnvironment>()
+                .call(to_acc)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("on_received")))
+                        .push_arg(from_acc)
+                        .push_arg(received_val)
+                        .push_arg(data_vec),
+                )
+                .returns::<core::result::Result<(), u8>>()
+                .try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::ReceiverRejected)
+            }
+            Ok(())
+        }
+
+        /// Transfer to several recipients in one call; the whole batch fails if any leg does.
+        /// Lists longer than `max_batch_size` are rejected with `Overflow`.
+        #[ink(message)]
+        pub fn batch_transfer(&mut self, transfers_vec: Vec<(AccountId, Balance)>) -> Result<()> {
+            self.check_batch_len(transfers_vec.len())?;
+            for (to_acc, amount_val) in transfers_vec {
+                self.transfer(to_acc, amount_val)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_max_batch_size(&mut self, max_batch_size_u32: u32) -> Result<()> {
+            self.only_owner()?;
+            if max_batch_size_u32 == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.max_batch_size_u32 = max_batch_size_u32;
+            self.env().emit_event(MaxBatchSizeSet { max_batch_size_u32 });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_batch_size(&self) -> u32 {
+            self.max_batch_size_u32
+        }
+
+        /// Launch-phase anti-bot: an amount an account receives can't be sent on with
+        /// `transfer`/`transfer_from` for `cooldown_blocks` blocks; the rest of its balance
+        /// stays free. Each account has two lock slots and a new receipt only ever extends
+        /// the most recent one, so unsolicited transfers can't keep earlier receipts locked
+        /// for more than about two cooldowns. Fee-exempt accounts (pools, NFMoo) are never
+        /// locked, nor is unstaked principal. Mints lock too unless `exempt_mints_flag`. Zero
+        /// blocks turns the feature off and releases every pending lock.
+        #[ink(message)]
+        pub fn set_receive_cooldown(&mut self, cooldown_blocks: u32, exempt_mints_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.receive_cooldown_blocks = cooldown_blocks;
+            self.cooldown_exempt_mints = exempt_mints_flag;
+            self.env().emit_event(ReceiveCooldownSet { cooldown_blocks, exempt_mints_flag });
+            Ok(())
+        }
+
+        /// First block at which everything `acc` received is free again; 0 if nothing is locked.
+        #[ink(message)]
+        pub fn receive_unlock_block_of(&self, acc: AccountId) -> u32 {
+            self.active_receive_locks(acc).iter().map(|(_, unlock_block)| *unlock_block).max().unwrap_or(0)
+        }
+
+        /// Part of `acc`'s balance still under the receive cooldown.
+        #[ink(message)]
+        pub fn receive_locked_of(&self, acc: AccountId) -> Balance {
+            self.active_receive_locks(acc)
+                .iter()
+                .fold(0, |sum_val: Balance, (amount_val, _)| sum_val.saturating_add(*amount_val))
+        }
+
+        /// Skim `fee_bps` (at most `MAX_TRANSFER_FEE_BPS`) of every balance move to
+        /// `fee_recipient_acc`; the sender pays `amount` and the receiver gets the rest.
+        #[ink(message)]
+        pub fn set_transfer_fee(&mut self, fee_bps: u16, fee_recipient_acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            if fee_bps > MAX_TRANSFER_FEE_BPS {
+                return Err(Error::InvalidFee)
+            }
+            self.transfer_fee_bps = fee_bps;
+            self.fee_recipient_acc = fee_recipient_acc;
+            self.env().emit_event(TransferFeeSet { fee_bps, fee_recipient_acc });
+            Ok(())
+        }
+
+        /// `(fee_bps, fee_recipient_acc)`.
+        #[ink(message)]
+        pub fn transfer_fee(&self) -> (u16, AccountId) {
+            (self.transfer_fee_bps, self.fee_recipient_acc)
+        }
+
+        /// Route `fee_bps` (at most `MAX_TRANSFER_FEE_BPS`) of every balance move to an NFMoo
+        /// collection's treasury to fund creator rewards, on top of the transfer fee. Same
+        /// exemptions as the transfer fee; the treasury itself is exempt. `None` (or 0 bps)
+        /// disables it.
+        #[ink(message)]
+        pub fn set_creator_fee(&mut self, fee_bps: u16, creator_treasury_opt: Option<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            if fee_bps > MAX_TRANSFER_FEE_BPS {
+                return Err(Error::InvalidFee)
+            }
+            self.creator_fee_bps = fee_bps;
+            self.creator_treasury_opt = creator_treasury_opt;
+            self.env().emit_event(CreatorFeeSet { fee_bps, creator_treasury_opt });
+            Ok(())
+        }
+
+        /// `(creator_fee_bps, creator_treasury_opt)`.
+        #[ink(message)]
+        pub fn creator_fee(&self) -> (u16, Option<AccountId>) {
+            (self.creator_fee_bps, self.creator_treasury_opt)
+        }
+
+        /// Burn `burn_bps` (at most `MAX_BURN_BPS`) of every balance move: the receiver gets
+        /// the rest and `total_supply` shrinks, never below `supply_floor`. Unlike the
+        /// transfer fee nothing is redirected. Mints, burns and moves touching a
+        /// fee-exempt account are not taxed.
+        #[ink(message)]
+        pub fn set_burn_bps(&mut self, burn_bps: u16) -> Result<()> {
+            self.only_owner()?;
+            if burn_bps > MAX_BURN_BPS {
+                return Err(Error::InvalidFee)
+            }
+            self.burn_bps_u16 = burn_bps;
+            self.env().emit_event(BurnBpsSet { burn_bps });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn burn_bps(&self) -> u16 {
+            self.burn_bps_u16
+        }
+
+        /// With `consolidated_flag` set, a move that pays a transfer fee, burn or creator fee
+        /// emits a single `TransferredWithFee` instead of the raw events (`Transferred` net
+        /// to the receiver and fee to the recipient, `Burned`, `CreatorFeePaid`). Moves
+        /// without deductions always emit `Transferred`.
+        #[ink(message)]
+        pub fn set_consolidated_fee_event(&mut self, consolidated_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.consolidated_fee_event_flag = consolidated_flag;
+            self.env().emit_event(FeeEventModeSet { consolidated_flag });
+            Ok(())
+        }
+
+        /// `true` while fee transfers are reported as one `TransferredWithFee`.
+        #[ink(message)]
+        pub fn consolidated_fee_event(&self) -> bool {
+            self.consolidated_fee_event_flag
+        }
+
+        /// Moves where either side is exempt (DEX pools, vesting contracts, ...) pay no fee.
+        /// Exempt accounts are also never held by the receive cooldown.
+        #[ink(message)]
+        pub fn set_fee_exempt(&mut self, acc: AccountId, exempt_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            if exempt_flag {
+                self.fee_exempt.insert(&acc, &true);
+            } else {
+                self.fee_exempt.remove(&acc);
+            }
+            self.env().emit_event(FeeExemptSet { acc, exempt_flag });
+            Ok(())
+        }
+
+        /// Listed accounts, plus the contract itself (stakes), the fee recipient and the
+        /// burn address while burns route there, are always exempt.
+        #[ink(message)]
+        pub fn is_fee_exempt(&self, acc: AccountId) -> bool {
+            self.fee_exempt.get(&acc).unwrap_or(false)
+                || acc == self.env().account_id()
+                || acc == self.fee_recipient_acc
+                || Some(acc) == self.creator_treasury_opt
+                || (self.burn_to_dead_flag && acc == self.dead_acc)
+        }
+
+        /// Open `transfer`/`transfer_from` to everyone. One-way: there is no way to
+        /// disable trading again. Until then only admin-held balances can move (so the
+        /// owner can seed liquidity); minting is never gated.
+        #[ink(message)]
+        pub fn enable_trading(&mut self) -> Result<()> {
+            self.only_owner()?;
+            if !self.trading_enabled {
+                self.trading_enabled = true;
+                self.env().emit_event(TradingEnabled { by_acc: self.env().caller() });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn trading_enabled(&self) -> bool {
+            self.trading_enabled
+        }
+
+        /// Largest balance a non-exempt account may reach by receiving a transfer or mint
+        /// (`None` = uncapped). Only the receiving side is checked: sends, burns and unstaked
+        /// principal always go through, and balances already above a newly lowered cap are left alone.
+        #[ink(message)]
+        pub fn set_max_balance_per_account(&mut self, max_balance_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            self.max_balance_per_account = max_balance_opt;
+            self.env().emit_event(MaxBalanceSet { max_balance_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_balance_per_account(&self) -> Option<Balance> {
+            self.max_balance_per_account
+        }
+
+        #[ink(message)]
+        pub fn set_balance_cap_exempt(&mut self, acc: AccountId, exempt_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            if exempt_flag {
+                self.balance_cap_exempt.insert(&acc, &true);
+            } else {
+                self.balance_cap_exempt.remove(&acc);
+            }
+            self.env().emit_event(BalanceCapExemptSet { acc, exempt_flag });
+            Ok(())
+        }
+
+        /// Explicitly exempt, or implicitly: the contract itself (staking pool) and, when
+        /// routing burns, `dead_acc`. Transfer-fee credits are never capped.
+        #[ink(message)]
+        pub fn is_balance_cap_exempt(&self, acc: AccountId) -> bool {
+            self.balance_cap_exempt.get(&acc).unwrap_or(false)
+                || acc == self.env().account_id()
+                || (self.burn_to_dead_flag && acc == self.dead_acc)
+        }
+
+        /// Reject `transfer`/`transfer_from` amounts below `min_transfer_amount` (burns and
+        /// mints are unaffected), so dust can't be sprayed to create throwaway holders.
+        /// Zero disables the check.
+        #[ink(message)]
+        pub fn set_min_transfer_amount(&mut self, min_transfer_amount: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.min_transfer_amount = min_transfer_amount;
+            self.env().emit_event(MinTransferAmountSet { min_transfer_amount });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_transfer_amount(&self) -> Balance {
+            self.min_transfer_amount
+        }
+
+        /// When enabled, `transfer` to yourself succeeds as a balance-preserving no-op that
+        /// still emits `Transferred` (the amount must be covered by the balance). When
+        /// disabled it fails with `SameAccount`. `transfer_from` always rejects `from == to`.
+        #[ink(message)]
+        pub fn set_allow_self_transfer(&mut self, allowed_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.allow_self_transfer = allowed_flag;
+            self.env().emit_event(SelfTransferPolicySet { allowed_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn allow_self_transfer(&self) -> bool {
+            self.allow_self_transfer
+        }
+
+        #[ink(message)]
+        pub fn approve(&mut self, spender_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            self.approve_internal(owner_acc, spender_acc, amount_val, false)
+        }
+
+        /// Compare-and-set approve: sets the allowance to `new_val` only if it is currently
+        /// `expected_val`, else `AllowanceRace`. Unlike `approve` it allows nonzero ->
+        /// nonzero, since the caller has proven it saw the value it is replacing.
+        #[ink(message)]
+        pub fn approve_checked(&mut self, spender_acc: AccountId, expected_val: Balance, new_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            if self.allowance(owner_acc, spender_acc) != expected_val {
+                return Err(Error::AllowanceRace)
+            }
+            self.approve_internal(owner_acc, spender_acc, new_val, true)
+        }
+
+        /// Approve `spender_acc` for `total_limit` and cap everything it may ever pull via
+        /// `transfer_from` or `burn_from` at that amount, however the allowance is topped up later
+        /// (`SpendLimitExceeded`). Restarts the spent counter; `total_limit = 0` revokes
+        /// the allowance and removes the cap.
+        #[ink(message)]
+        pub fn approve_with_limit(&mut self, spender_acc: AccountId, total_limit: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            self.approve_internal(owner_acc, spender_acc, total_limit, true)?;
+            if total_limit == 0 {
+                self.spend_limit.remove(&(owner_acc, spender_acc));
+            } else {
+                self.spend_limit.insert(&(owner_acc, spender_acc), &total_limit);
+            }
+            self.operator_spent.remove(&(owner_acc, spender_acc));
+            self.env().emit_event(SpendLimitSet { owner_acc, spender_acc, total_limit });
+            Ok(())
+        }
+
+        /// Lifetime cap set by `approve_with_limit`, if any.
+        #[ink(message)]
+        pub fn spend_limit_of(&self, owner_acc: AccountId, spender_acc: AccountId) -> Option<Balance> {
+            self.spend_limit.get(&(owner_acc, spender_acc))
+        }
+
+        /// Amount pulled by `spender_acc` under its current spend limit.
+        #[ink(message)]
+        pub fn spent_of(&self, owner_acc: AccountId, spender_acc: AccountId) -> Balance {
+            self.operator_spent.get(&(owner_acc, spender_acc)).unwrap_or(0)
+        }
+
+        /// Cap how many spenders an account may have a nonzero allowance for at once; a new
+        /// nonzero approval (or permit) past the cap fails with `TooManyAllowances`.
+        /// Allowances spent or reset to zero free their slot. `None` disables it.
+        #[ink(message)]
+        pub fn set_max_active_allowances(&mut self, max_allowances_opt: Option<u32>) -> Result<()> {
+            self.only_owner()?;
+            self.max_active_allowances = max_allowances_opt;
+            self.env().emit_event(MaxActiveAllowancesSet { max_allowances_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_active_allowances(&self) -> Option<u32> {
+            self.max_active_allowances
+        }
+
+        /// Spenders `owner_acc` currently has a nonzero allowance for.
+        #[ink(message)]
+        pub fn active_allowance_count(&self, owner_acc: AccountId) -> u32 {
+            self.active_allowance_cnt.get(&owner_acc).unwrap_or(0)
+        }
+
+        /// Approve several spenders in one call, one `Approved` per changed entry. With
+        /// `force_flag` unset each entry follows the safe-approve rule and any
+        /// `AllowanceRace` fails the whole batch; with it set, allowances are overwritten.
+        /// Lists longer than `max_batch_size` are rejected with `Overflow`.
+        #[ink(message)]
+        pub fn batch_approve(&mut self, approvals_vec: Vec<(AccountId, Balance)>, force_flag: bool) -> Result<()> {
+            self.when_not_paused()?;
+            self.check_batch_len(approvals_vec.len())?;
+            let owner_acc = self.env().caller();
+            for (spender_acc, amount_val) in approvals_vec {
+                self.approve_internal(owner_acc, spender_acc, amount_val, force_flag)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender_acc: AccountId, add_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
+            let new_val = current_val.checked_add(add_val).ok_or(Error::Overflow)?;
+            self.write_allowance(owner_acc, spender_acc, new_val)?;
+            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val: new_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender_acc: AccountId, sub_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
+            let new_val = current_val.saturating_sub(sub_val);
+            self.write_allowance(owner_acc, spender_acc, new_val)?;
+            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val: new_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from_acc: AccountId,
+            to_acc: AccountId,
+            amount_val: Balance,
+        ) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.check_min_transfer(amount_val)?;
+            if from_acc == to_acc {
+                return Err(Error::SameAccount)
+            }
+            self.when_trading(from_acc)?;
+            self.when_cooled_down(from_acc, amount_val)?;
+
+            // Precheck balances to avoid burning allowance on failure
+            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
+            if from_bal < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+
+            // Check allowance
+            let caller_acc = self.env().caller();
+            self.when_spender_allowed(caller_acc)?;
+            let current_allow = self.allowances.get(&(from_acc, caller_acc)).unwrap_or(0);
+            if current_allow < amount_val {
+                return Err(Error::InsufficientAllowance)
+            }
+            self.spend_within_limit(from_acc, caller_acc, amount_val)?;
+
+            // Move balances (overflow-safe)
+            self.move_balance(from_acc, to_acc, amount_val)?;
+
+            // Reduce allowance last
+            let new_allow = current_allow - amount_val;
+            self.write_allowance(from_acc, caller_acc, new_allow)?;
+            Ok(())
+        }
+
+        /// `transfer_from` for each `(from, to, amount)` leg, each spending the caller's
+        /// allowance on that leg's `from`. Atomic: any failing leg reverts the whole batch.
+        /// Lists longer than `max_batch_size` are rejected with `Overflow`.
+        #[ink(message)]
+        pub fn batch_transfer_from(&mut self, transfers_vec: Vec<(AccountId, AccountId, Balance)>) -> Result<()> {
+            self.check_batch_len(transfers_vec.len())?;
+            for (from_acc, to_acc, amount_val) in transfers_vec {
+                self.transfer_from(from_acc, to_acc, amount_val)?;
+            }
+            Ok(())
+        }
+
+        /// Set `allowance(owner_acc, spender_acc) = amount_val` from the owner's ECDSA
+        /// signature over `(contract, owner, spender, amount, deadline_ms, nonce)`, where
+        /// `nonce` is `permit_nonce(owner_acc)`. Bypasses the safe-approve zero-first rule.
+        #[ink(message)]
+        pub fn permit(
+            &mut self,
+            owner_acc: AccountId,
+            spender_acc: AccountId,
+            amount_val: Balance,
+            deadline_ms: u64,
+            signature_val: [u8; 65],
+        ) -> Result<()> {
+            self.when_not_paused()?;
+            self.apply_permit(owner_acc, spender_acc, amount_val, deadline_ms, &signature_val)
+        }
+
+        #[ink(message)]
+        pub fn permit_nonce(&self, owner_acc: AccountId) -> u64 {
+            self.permit_nonces.get(&owner_acc).unwrap_or(0)
+        }
+
+        /// Approve-and-pull in one call: applies `from_acc`'s permit naming the caller as
+        /// spender for `amount_val`, then runs `transfer_from`. Fails with `PermitExpired`
+        /// or `InvalidSignature` before any tokens move.
+        #[ink(message)]
+        pub fn transfer_from_with_permit(
+            &mut self,
+            from_acc: AccountId,
+            to_acc: AccountId,
+            amount_val: Balance,
+            deadline_ms: u64,
+            signature_val: [u8; 65],
+        ) -> Result<()> {
+            self.when_not_paused()?;
+            let spender_acc = self.env().caller();
+            self.apply_permit(from_acc, spender_acc, amount_val, deadline_ms, &signature_val)?;
+            self.transfer_from(from_acc, to_acc, amount_val)
+        }
+
+        // -------- snapshots / dividends --------
+
+        /// Start a new snapshot; balances as of this call stay queryable via `balance_of_at`.
+        #[ink(message)]
+        pub fn snapshot(&mut self) -> Result<u32> {
+            self.only_owner()?;
+            self.take_snapshot()
+        }
+
+        #[ink(message)]
+        pub fn current_snapshot_id(&self) -> u32 {
+            self.snapshot_id
+        }
+
+        /// Total supply recorded when `snapshot_id` was taken; the denominator matching
+        /// `balance_of_at` for the same id.
+        #[ink(message)]
+        pub fn total_supply_at(&self, snapshot_id: u32) -> Result<Balance> {
+            if snapshot_id == 0 || snapshot_id > self.snapshot_id {
+                return Err(Error::InvalidSnapshot)
+            }
+            self.supply_at.get(&snapshot_id).ok_or(Error::InvalidSnapshot)
+        }
+
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner_acc: AccountId, snapshot_id: u32) -> Result<Balance> {
+            if snapshot_id == 0 || snapshot_id > self.snapshot_id {
+                return Err(Error::InvalidSnapshot)
+            }
+            Ok(self
+                .checkpoint_at(owner_acc, snapshot_id)
+                .unwrap_or_else(|| self.balance_of(owner_acc)))
+        }
+
+        /// Owner deposits native currency for holders; snapshots balances and records the
+        /// supply outside the contract (stakes excluded, since the contract never claims) as
+        /// the denominator, so the whole pool is claimable. The epoch id is the new snapshot id.
+        #[ink(message, payable)]
+        pub fn deposit_dividend(&mut self) -> Result<u32> {
+            self.only_owner()?;
+            let pool_val = self.env().transferred_value();
+            let supply_val = self.total_supply.saturating_sub(self.balance_of(self.env().account_id()));
+            if pool_val == 0 || supply_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let epoch_u32 = self.take_snapshot()?;
+            self.dividends.insert(&epoch_u32, &(pool_val, supply_val));
+            self.dividend_pending = self.dividend_pending.checked_add(pool_val).ok_or(Error::Overflow)?;
+            self.env().emit_event(DividendDeposited { epoch_u32, pool_val, supply_val });
+            Ok(epoch_u32)
+        }
+
+        /// Pay the caller `balance_at(epoch) * pool / supply`, once per epoch, where `supply`
+        /// is the denominator recorded by `deposit_dividend`. Tokens parked at the contract
+        /// (e.g. stakes) earn no dividend and aren't part of that denominator.
+        #[ink(message)]
+        pub fn claim_dividend(&mut self, epoch_u32: u32) -> Result<Balance> {
+            let (pool_val, supply_val) = self.dividends.get(&epoch_u32).ok_or(Error::InvalidSnapshot)?;
+            let holder_acc = self.env().caller();
+            if self.dividend_claimed.get(&(epoch_u32, holder_acc)).unwrap_or(false) {
+                return Err(Error::AlreadyClaimed)
+            }
+            let held_val = self.balance_of_at(holder_acc, epoch_u32)?;
+            let amount_val = held_val.checked_mul(pool_val).ok_or(Error::Overflow)? / supply_val;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.dividend_claimed.insert(&(epoch_u32, holder_acc), &true);
+            self.dividend_pending = self.dividend_pending.saturating_sub(amount_val);
+            self.env()
+                .transfer(holder_acc, amount_val)
+                .map_err(|_| Error::NativeTransferFailed)?;
+            self.env().emit_event(DividendClaimed { epoch_u32, holder_acc, amount_val });
+            Ok(amount_val)
+        }
+
+        #[ink(message)]
+        pub fn dividend_of(&self, epoch_u32: u32) -> Option<(Balance, Balance)> {
+            self.dividends.get(&epoch_u32)
+        }
+
+        #[ink(message)]
+        pub fn is_dividend_claimed(&self, epoch_u32: u32, holder_acc: AccountId) -> bool {
+            self.dividend_claimed.get(&(epoch_u32, holder_acc)).unwrap_or(false)
+        }
+
+        // -------- staking --------
+
+        /// Owner-set reward per `REWARD_SCALE` staked units per block. Accrual up to now
+        /// is settled at the old rate first, so changes are never retroactive.
+        #[ink(message)]
+        pub fn set_reward_rate(&mut self, rate_val: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.reward_acc_val = self.reward_acc_now()?;
+            self.reward_acc_block = self.env().block_number();
+            self.reward_rate_per_block = rate_val;
+            self.env().emit_event(RewardRateSet { rate_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn stake(&mut self, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let staker_acc = self.env().caller();
+            self.settle_rewards(staker_acc)?;
+            self.move_balance(staker_acc, self.env().account_id(), amount_val)?;
+            let current_stake = self.staked_of(staker_acc);
+            if current_stake == 0 {
+                self.staker_cnt = self.staker_cnt.checked_add(1).ok_or(Error::Overflow)?;
+            }
+            let new_stake = current_stake.checked_add(amount_val).ok_or(Error::Overflow)?;
+            self.staked.insert(&staker_acc, &new_stake);
+            self.total_staked = self.total_staked.checked_add(amount_val).ok_or(Error::Overflow)?;
+            self.sync_reward_debt(staker_acc)?;
+            self.env().emit_event(Staked { staker_acc, amount_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unstake(&mut self, amount_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let staker_acc = self.env().caller();
+            let current_stake = self.staked_of(staker_acc);
+            if current_stake < amount_val {
+                return Err(Error::InsufficientStake)
+            }
+            // principal must always come back: if rewards can't be settled, the unsettled
+            // part is forfeited instead of locking the stake
+            let settled_flag = self.settle_rewards(staker_acc).is_ok();
+            self.staked.insert(&staker_acc, &(current_stake - amount_val));
+            if current_stake == amount_val {
+                self.staker_cnt = self.staker_cnt.saturating_sub(1);
+            }
+            self.total_staked = self.total_staked.checked_sub(amount_val).ok_or(Error::Overflow)?;
+            if self.sync_reward_debt(staker_acc).is_err() || !settled_flag {
+                // nothing accrues on what's left until a computable settlement
+                let debt_val = self.reward_debt_for(staker_acc).unwrap_or(Balance::MAX);
+                self.reward_debt.insert(&staker_acc, &debt_val);
+            }
+            self.return_stake(staker_acc, amount_val)?;
+            self.env().emit_event(Unstaked { staker_acc, amount_val });
+            Ok(())
+        }
+
+        /// Mint all accrued rewards to the caller. The contract itself must hold `ROLE_MINTER`.
+        /// Rewards count against the era cap; past it the claim fails and stays owed.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self) -> Result<()> {
+            self.when_not_paused()?;
+            if !self.has_role(ROLE_MINTER, self.env().account_id()) {
+                return Err(Error::Unauthorized)
+            }
+            let staker_acc = self.env().caller();
+            self.settle_rewards(staker_acc)?;
+            let amount_val = self.reward_owed.get(&staker_acc).unwrap_or(0);
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.spend_era_budget(amount_val)?;
+            self.reward_owed.remove(&staker_acc);
+            self.mint_internal(staker_acc, amount_val)?;
+            self.env().emit_event(RewardsClaimed { staker_acc, amount_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn staked_of(&self, staker_acc: AccountId) -> Balance {
+            self.staked.get(&staker_acc).unwrap_or(0)
+        }
+
+        /// Sum of all stakes (the staking TVL, in base units), kept up to date by
+        /// `stake`/`unstake` so it's a single read.
+        #[ink(message)]
+        pub fn total_staked(&self) -> Balance {
+            self.total_staked
+        }
+
+        /// Accounts with a nonzero stake.
+        #[ink(message)]
+        pub fn staker_count(&self) -> u32 {
+            self.staker_cnt
+        }
+
+        #[ink(message)]
+        pub fn reward_rate_per_block(&self) -> Balance {
+            self.reward_rate_per_block
+        }
+
+        #[ink(message)]
+        pub fn pending_rewards(&self, staker_acc: AccountId) -> Balance {
+            let owed_val = self.reward_owed.get(&staker_acc).unwrap_or(0);
+            let accrued_val = self
+                .reward_acc_now()
+                .and_then(|acc_val| self.accrued_since_debt(staker_acc, acc_val))
+                .unwrap_or(0);
+            owed_val.saturating_add(accrued_val)
+        }
+
+        // ---- internals ----
+
+        fn check_mint_per_call(&self, amount_val: Balance) -> Result<()> {
+            if self.max_mint_per_call != 0 && amount_val > self.max_mint_per_call {
+                return Err(Error::MintPerCallExceeded)
+            }
+            Ok(())
+        }
+
+        /// Charge `amount_val` to the current era, rolling the counter over at era boundaries.
+        fn spend_era_budget(&mut self, amount_val: Balance) -> Result<()> {
+            if self.era_length_blocks == 0 {
+                return Ok(())
+            }
+            if amount_val > self.era_remaining() {
+                return Err(Error::EraCapExceeded)
+            }
+            let era_now = self.current_era();
+            if self.era_index != era_now {
+                self.era_index = era_now;
+                self.era_minted = 0;
+            }
+            self.era_minted = self.era_minted.checked_add(amount_val).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        fn approve_internal(
+            &mut self,
+            owner_acc: AccountId,
+            spender_acc: AccountId,
+            amount_val: Balance,
+            force_flag: bool,
+        ) -> Result<()> {
+            let current_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
+            // Re-approving the current value is a silent no-op: no write, no event
+            if current_val == amount_val {
+                return Ok(())
+            }
+            // Safe-approve: forbid nonzero -> nonzero without zeroing first
+            if !force_flag && current_val != 0 && amount_val != 0 {
+                return Err(Error::AllowanceRace)
+            }
+            self.write_allowance(owner_acc, spender_acc, amount_val)?;
+            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val });
+            Ok(())
+        }
+
+        /// Count `amount_val` against the spender's lifetime limit, if one is set.
+        fn spend_within_limit(&mut self, owner_acc: AccountId, spender_acc: AccountId, amount_val: Balance) -> Result<()> {
+            if let Some(limit_val) = self.spend_limit.get(&(owner_acc, spender_acc)) {
+                let spent_val = self
+                    .spent_of(owner_acc, spender_acc)
+                    .checked_add(amount_val)
+                    .ok_or(Error::Overflow)?;
+                if spent_val > limit_val {
+                    return Err(Error::SpendLimitExceeded)
+                }
+                self.operator_spent.insert(&(owner_acc, spender_acc), &spent_val);
+            }
+            Ok(())
+        }
+
+        fn check_balance_cap(&self, acc: AccountId, new_bal: Balance) -> Result<()> {
+            if let Some(max_balance_val) = self.max_balance_per_account {
+                if new_bal > max_balance_val && !self.is_balance_cap_exempt(acc) {
+                    return Err(Error::BalanceCapExceeded)
+                }
+            }
+            Ok(())
+        }
+
+        fn when_trading(&self, from_acc: AccountId) -> Result<()> {
+            if !self.trading_enabled && !self.has_role(ROLE_ADMIN, from_acc) {
+                return Err(Error::TradingDisabled)
+            }
+            Ok(())
+        }
+
+        /// Sending `amount_val` must leave the still-locked received amount in place. Overdrafts
+        /// are left to the balance check so they keep reporting `InsufficientBalance`.
+        fn when_cooled_down(&self, acc: AccountId, amount_val: Balance) -> Result<()> {
+            let balance_val = self.balance_of(acc);
+            if amount_val <= balance_val && balance_val - amount_val < self.receive_locked_of(acc) {
+                return Err(Error::Cooldown)
+            }
+            Ok(())
+        }
+
+        /// Lock slots of `acc` with elapsed ones emptied; all empty while the feature is off.
+        fn active_receive_locks(&self, acc: AccountId) -> [(Balance, u32); 2] {
+            if self.receive_cooldown_blocks == 0 {
+                return [(0, 0); 2]
+            }
+            let now_block = self.env().block_number();
+            self.receive_locks
+                .get(&acc)
+                .unwrap_or_default()
+                .map(|slot_val| if slot_val.1 > now_block { slot_val } else { (0, 0) })
+        }
+
+        /// Lock `amount_val` just received by `acc`: into an empty slot if there is one,
+        /// otherwise merged into the slot that unlocks last, which restarts only that slot.
+        fn start_cooldown(&mut self, acc: AccountId, amount_val: Balance) {
+            if self.receive_cooldown_blocks == 0 || amount_val == 0 || self.is_fee_exempt(acc) {
+                return
+            }
+            let unlock_block = self.env().block_number().saturating_add(self.receive_cooldown_blocks);
+            let mut locks_arr = self.active_receive_locks(acc);
+            let slot_idx = match locks_arr.iter().position(|(locked_val, _)| *locked_val == 0) {
+                Some(free_idx) => free_idx,
+                None if locks_arr[0].1 >= locks_arr[1].1 => 0,
+                None => 1,
+            };
+            locks_arr[slot_idx] = (locks_arr[slot_idx].0.saturating_add(amount_val), unlock_block);
+            self.receive_locks.insert(&acc, &locks_arr);
+        }
+
+        fn check_min_transfer(&self, amount_val: Balance) -> Result<()> {
+            if amount_val < self.min_transfer_amount {
+                return Err(Error::BelowMinimum)
+            }
+            Ok(())
+        }
+
+        fn check_batch_len(&self, len_val: usize) -> Result<()> {
+            if len_val > self.max_batch_size_u32 as usize {
+                return Err(Error::Overflow)
+            }
+            Ok(())
+        }
+
+        /// Single write path for allowances so the active-allowance count stays exact.
+        fn write_allowance(&mut self, owner_acc: AccountId, spender_acc: AccountId, new_val: Balance) -> Result<()> {
+            let old_val = self.allowances.get(&(owner_acc, spender_acc)).unwrap_or(0);
+            let active_cnt = self.active_allowance_count(owner_acc);
+            if old_val == 0 && new_val != 0 {
+                if let Some(max_allowances) = self.max_active_allowances {
+                    if active_cnt >= max_allowances {
+                        return Err(Error::TooManyAllowances)
+                    }
+                }
+                self.active_allowance_cnt.insert(&owner_acc, &active_cnt.checked_add(1).ok_or(Error::Overflow)?);
+            } else if old_val != 0 && new_val == 0 {
+                self.active_allowance_cnt.insert(&owner_acc, &active_cnt.saturating_sub(1));
+            }
+            self.allowances.insert(&(owner_acc, spender_acc), &new_val);
+            Ok(())
+        }
+
+        /// Single write path for balances so snapshots and the holder set see every change.
+        fn write_balance(&mut self, acc: AccountId, new_bal: Balance) -> Result<()> {
+            self.checkpoint_balance(acc)?;
+            let old_bal = self.balance_of(acc);
+            if old_bal == 0 && new_bal != 0 {
+                self.add_holder(acc)?;
+            } else if old_bal != 0 && new_bal == 0 {
+                self.remove_holder(acc)?;
+            }
+            if new_bal < old_bal {
+                self.void_reversible_receipts(acc);
+            }
+            self.balances.insert(&acc, &new_bal);
+            self.last_activity.insert(&acc, &self.env().block_timestamp());
+            Ok(())
+        }
+
+        /// A debit spends received reversible funds first, so it voids every open receipt of
+        /// `acc` by moving its generation on. Writes only once per batch of receipts.
+        fn void_reversible_receipts(&mut self, acc: AccountId) {
+            if let Some((gen_u32, true)) = self.reversible_gen.get(&acc) {
+                self.reversible_gen.insert(&acc, &(gen_u32.wrapping_add(1), false));
+            }
+        }
+
+        fn add_holder(&mut self, acc: AccountId) -> Result<()> {
+            let index_val = self.holder_cnt;
+            self.holder_by_index.insert(&index_val, &acc);
+            self.holder_index.insert(&acc, &index_val);
+            self.holder_cnt = index_val.checked_add(1).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        fn remove_holder(&mut self, acc: AccountId) -> Result<()> {
+            let index_val = self.holder_index.get(&acc).ok_or(Error::Overflow)?;
+            let last_index = self.holder_cnt.checked_sub(1).ok_or(Error::Overflow)?;
+            if index_val != last_index {
+                let last_acc = self.holder_by_index.get(&last_index).ok_or(Error::Overflow)?;
+                self.holder_by_index.insert(&index_val, &last_acc);
+                self.holder_index.insert(&last_acc, &index_val);
+            }
+            self.holder_by_index.remove(&last_index);
+            self.holder_index.remove(&acc);
+            self.holder_cnt = last_index;
+            Ok(())
+        }
+
+        fn take_snapshot(&mut self) -> Result<u32> {
+            self.snapshot_id = self.snapshot_id.checked_add(1).ok_or(Error::Overflow)?;
+            let snapshot_id = self.snapshot_id;
+            self.supply_at.insert(&snapshot_id, &self.total_supply);
+            self.env().emit_event(Snapshot { snapshot_id });
+            Ok(snapshot_id)
+        }
+
+        /// Before the first change since the latest snapshot, record the pre-change balance.
+        fn checkpoint_balance(&mut self, acc: AccountId) -> Result<()> {
+            if self.snapshot_id == 0 {
+                return Ok(())
+            }
+            let count_val = self.bal_ckpt_cnt.get(&acc).unwrap_or(0);
+            let last_id = match count_val.checked_sub(1) {
+                Some(last_index) => self.bal_ckpt.get(&(acc, last_index)).map(|(id, _)| id).unwrap_or(0),
+                None => 0,
+            };
+            if last_id < self.snapshot_id {
+                self.bal_ckpt.insert(&(acc, count_val), &(self.snapshot_id, self.balance_of(acc)));
+                self.bal_ckpt_cnt.insert(&acc, &count_val.checked_add(1).ok_or(Error::Overflow)?);
+            }
+            Ok(())
+        }
+
+        /// Value at the first checkpoint taken at or after `snapshot_id`, if any
+        /// (binary search over the account's ordered checkpoints).
+        fn checkpoint_at(&self, acc: AccountId, snapshot_id: u32) -> Option<Balance> {
+            let count_val = self.bal_ckpt_cnt.get(&acc).unwrap_or(0);
+            let (mut low_index, mut high_index) = (0u32, count_val);
+            while low_index < high_index {
+                let mid_index = low_index + (high_index - low_index) / 2;
+                let (mid_id, _) = self.bal_ckpt.get(&(acc, mid_index))?;
+                if mid_id < snapshot_id {
+                    low_index = mid_index + 1;
+                } else {
+                    high_index = mid_index;
+                }
+            }
+            if low_index == count_val {
+                return None
+            }
+            self.bal_ckpt.get(&(acc, low_index)).map(|(_, bal_val)| bal_val)
+        }
+
+        /// Cumulative reward per `REWARD_SCALE` staked units, brought up to the current block.
+        fn reward_acc_now(&self) -> Result<Balance> {
+            let elapsed_blocks = self.env().block_number().saturating_sub(self.reward_acc_block);
+            let delta_val = self
+                .reward_rate_per_block
+                .checked_mul(Balance::from(elapsed_blocks))
+                .ok_or(Error::Overflow)?;
+            self.reward_acc_val.checked_add(delta_val).ok_or(Error::Overflow)
+        }
+
+        fn accrued_since_debt(&self, staker_acc: AccountId, acc_val: Balance) -> Result<Balance> {
+            let gross_val = Self::scale_reward(self.staked_of(staker_acc), acc_val)?;
+            Ok(gross_val.saturating_sub(self.reward_debt.get(&staker_acc).unwrap_or(0)))
+        }
+
+        /// `stake_val * acc_val / REWARD_SCALE`, floored, without the full-width product:
+        /// with `x = q * SCALE + r`, `a * b / S = qa * b + ra * qb + ra * rb / S`, and
+        /// `ra * rb < S^2` always fits. Only fails if the result itself exceeds `u128`.
+        fn scale_reward(stake_val: Balance, acc_val: Balance) -> Result<Balance> {
+            let (stake_q, stake_r) = (stake_val / REWARD_SCALE, stake_val % REWARD_SCALE);
+            let (acc_q, acc_r) = (acc_val / REWARD_SCALE, acc_val % REWARD_SCALE);
+            stake_q
+                .checked_mul(acc_val)
+                .and_then(|val| val.checked_add(stake_r.checked_mul(acc_q)?))
+                .and_then(|val| val.checked_add(stake_r * acc_r / REWARD_SCALE))
+                .ok_or(Error::Overflow)
+        }
+
+        /// Move everything accrued since the staker's last interaction into `reward_owed`.
+        /// Must run before any change to their stake.
+        fn settle_rewards(&mut self, staker_acc: AccountId) -> Result<()> {
+            self.reward_acc_val = self.reward_acc_now()?;
+            self.reward_acc_block = self.env().block_number();
+            let accrued_val = self.accrued_since_debt(staker_acc, self.reward_acc_val)?;
+            if accrued_val > 0 {
+                let owed_val = self.reward_owed.get(&staker_acc).unwrap_or(0);
+                let new_owed = owed_val.checked_add(accrued_val).ok_or(Error::Overflow)?;
+                self.reward_owed.insert(&staker_acc, &new_owed);
+            }
+            self.sync_reward_debt(staker_acc)
+        }
+
+        fn sync_reward_debt(&mut self, staker_acc: AccountId) -> Result<()> {
+            let debt_val = self.reward_debt_for(staker_acc)?;
+            self.reward_debt.insert(&staker_acc, &debt_val);
+            Ok(())
+        }
+
+        fn reward_debt_for(&self, staker_acc: AccountId) -> Result<Balance> {
+            Self::scale_reward(self.staked_of(staker_acc), self.reward_acc_val)
+        }
+
+        fn set_role(&mut self, role_u8: u8, acc: AccountId, enabled_flag: bool) -> Result<()> {
+            if role_u8 > ROLE_BURNER {
+                return Err(Error::InvalidRole)
+            }
+            if self.has_role(role_u8, acc) != enabled_flag {
+                let cnt_val = self.role_count(role_u8);
+                let new_cnt = if enabled_flag {
+                    cnt_val.checked_add(1).ok_or(Error::Overflow)?
+                } else {
+                    cnt_val.saturating_sub(1)
+                };
+                self.role_cnt.insert(&role_u8, &new_cnt);
+            }
+            self.roles.insert(&(role_u8, acc), &enabled_flag);
+            let by_acc = self.env().caller();
+            if enabled_flag {
+                self.env().emit_event(RoleGranted { role_u8, acc, by_acc });
+            } else {
+                self.env().emit_event(RoleRevoked { role_u8, acc, by_acc });
+            }
+            Ok(())
+        }
+
+        fn self_transfer(&mut self, acc: AccountId, amount_val: Balance) -> Result<()> {
+            if !self.allow_self_transfer {
+                return Err(Error::SameAccount)
+            }
+            if self.balance_of(acc) < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+            self.env().emit_event(Transferred { from_acc: acc, to_acc: acc, amount_val });
+            Ok(())
+        }
+
+        fn burn_internal(&mut self, from_acc: AccountId, amount_val: Balance) -> Result<()> {
+            if self.burn_to_dead_flag {
+                if from_acc == self.dead_acc {
+                    return Err(Error::SameAccount)
+                }
+                // untaxed: `dead_acc` is fee-exempt while routing, so `move_balance` takes
+                // no transfer fee, burn share or creator fee
+                return self.move_balance(from_acc, self.dead_acc, amount_val)
+            }
+            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
+            if from_bal < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+            let new_total = self.total_supply.checked_sub(amount_val).ok_or(Error::Overflow)?;
+            if new_total < self.supply_floor {
+                return Err(Error::SupplyFloor)
+            }
+            let new_from_bal = from_bal.checked_sub(amount_val).ok_or(Error::Overflow)?;
+            self.write_balance(from_acc, new_from_bal)?;
+            self.total_supply = new_total;
+            self.env().emit_event(Burned { from_acc, amount_val });
+            Ok(())
+        }
+
+        fn mint_internal(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.check_oracle_cap(amount_val)?;
+
+            let new_total = self.total_supply.checked_add(amount_val).ok_or(Error::Overflow)?;
+            self.total_supply = new_total;
+
+            let to_bal = self.balances.get(&to_acc).unwrap_or(0);
+            let new_to = to_bal.checked_add(amount_val).ok_or(Error::Overflow)?;
+            self.check_balance_cap(to_acc, new_to)?;
+            self.write_balance(to_acc, new_to)?;
+            if !self.cooldown_exempt_mints {
+                self.start_cooldown(to_acc, amount_val);
+            }
+
+            self.env().emit_event(Minted { to_acc, amount_val });
+            Ok(())
+        }
+
+        /// Every supply expansion is bounded by the oracle's current ceiling, when one is set.
+        fn check_oracle_cap(&self, amount_val: Balance) -> Result<()> {
+            let Some(oracle_acc) = self.oracle_acc_opt else {
+                return Ok(())
+            };
+            let call_res = build_call::<DefaultEnvironment>()
+                .call(oracle_acc)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("max_mintable"))))
+                .returns::<Balance>()
+                .try_invoke();
+            let ceiling_val = match call_res {
+                Ok(Ok(ceiling_val)) => ceiling_val,
+                _ => return Err(Error::OracleCallFailed),
+            };
+            self.env().emit_event(OracleCeilingChecked { oracle_acc, ceiling_val, amount_val });
+            if amount_val > ceiling_val {
+                return Err(Error::OracleCapExceeded)
+            }
+            Ok(())
+        }
+
+        fn apply_permit(
+            &mut self,
+            owner_acc: AccountId,
+            spender_acc: AccountId,
+            amount_val: Balance,
+            deadline_ms: u64,
+            signature_val: &[u8; 65],
+        ) -> Result<()> {
+            if self.env().block_timestamp() > deadline_ms {
+                return Err(Error::PermitExpired)
+            }
+            let nonce_val = self.permit_nonce(owner_acc);
+            let msg_hash = self.env().hash_encoded::<Blake2x256, _>(&(
+                self.env().account_id(),
+                owner_acc,
+                spender_acc,
+                amount_val,
+                deadline_ms,
+                nonce_val,
+            ));
+            if self.recover_signer(&msg_hash, signature_val)? != owner_acc {
+                return Err(Error::InvalidSignature)
+            }
+            self.permit_nonces.insert(&owner_acc, &nonce_val.checked_add(1).ok_or(Error::Overflow)?);
+            self.write_allowance(owner_acc, spender_acc, amount_val)?;
+            self.env().emit_event(Approved { owner_acc, spender_acc, amount_val });
+            Ok(())
+        }
+
+        fn emergency_stop(&mut self, by_acc: AccountId) {
+            self.paused_flag = true;
+            self.pause_reason = Some(PAUSE_REASON_INCIDENT);
+            self.env().emit_event(PausedSet { paused_flag: true });
+            self.env().emit_event(EmergencyPaused { by_acc });
+        }
+
+        /// Substrate ECDSA accounts are the blake2-256 of the compressed public key.
+        fn recover_signer(&self, msg_hash: &[u8; 32], signature_val: &[u8; 65]) -> Result<AccountId> {
+            let pub_key = self
+                .env()
+                .ecdsa_recover(signature_val, msg_hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            let acc_bytes = self.env().hash_bytes::<Blake2x256>(&pub_key);
+            Ok(AccountId::from(acc_bytes))
+        }
+
+        /// Credit unstaked principal back from custody. Not a receipt: neither the balance cap
+        /// nor the receive cooldown applies (and no fee could, the contract being exempt).
+        fn return_stake(&mut self, staker_acc: AccountId, amount_val: Balance) -> Result<()> {
+            let self_acc = self.env().account_id();
+            let self_bal = self.balances.get(&self_acc).unwrap_or(0);
+            if self_bal < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+            self.write_balance(self_acc, self_bal - amount_val)?;
+            let staker_bal = self.balances.get(&staker_acc).unwrap_or(0);
+            self.write_balance(staker_acc, staker_bal.checked_add(amount_val).ok_or(Error::Overflow)?)?;
+            self.env().emit_event(Transferred { from_acc: self_acc, to_acc: staker_acc, amount_val });
+            Ok(())
+        }
+
+        fn move_balance(&mut self, from_acc: AccountId, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            let from_bal = self.balances.get(&from_acc).unwrap_or(0);
+            if from_bal < amount_val {
+                return Err(Error::InsufficientBalance)
+            }
+            let new_from = from_bal.checked_sub(amount_val).ok_or(Error::Overflow)?;
+            self.write_balance(from_acc, new_from)?;
+
+            let exempt_flag = self.is_fee_exempt(from_acc) || self.is_fee_exempt(to_acc);
+            let fee_val = if self.transfer_fee_bps == 0 || exempt_flag {
+                0
+            } else {
+                amount_val.checked_mul(Balance::from(self.transfer_fee_bps)).ok_or(Error::Overflow)? / 10_000
+            };
+            let burn_val = if self.burn_bps_u16 == 0 || exempt_flag {
+                0
+            } else {
+                let burn_val =
+                    amount_val.checked_mul(Balance::from(self.burn_bps_u16)).ok_or(Error::Overflow)? / 10_000;
+                burn_val.min(self.total_supply.saturating_sub(self.supply_floor))
+            };
+            let creator_val = match self.creator_treasury_opt {
+                Some(_) if !exempt_flag => {
+                    amount_val.checked_mul(Balance::from(self.creator_fee_bps)).ok_or(Error::Overflow)? / 10_000
+                }
+                _ => 0,
+            };
+            let net_val = amount_val - fee_val - burn_val - creator_val;
+            let consolidated_flag = self.consolidated_fee_event_flag && net_val != amount_val;
+            if burn_val != 0 {
+                self.total_supply -= burn_val;
+                if !consolidated_flag {
+                    self.env().emit_event(Burned { from_acc, amount_val: burn_val });
+                }
+            }
+
+            let to_bal = self.balances.get(&to_acc).unwrap_or(0);
+            let new_to = to_bal.checked_add(net_val).ok_or(Error::Overflow)?;
+            self.check_balance_cap(to_acc, new_to)?;
+            self.write_balance(to_acc, new_to)?;
+            self.start_cooldown(to_acc, net_val);
+            if !consolidated_flag {
+                self.env().emit_event(Transferred { from_acc, to_acc, amount_val: net_val });
+            }
+
+            let fee_acc = self.fee_recipient_acc;
+            if fee_val != 0 {
+                let fee_bal = self.balances.get(&fee_acc).unwrap_or(0);
+                self.write_balance(fee_acc, fee_bal.checked_add(fee_val).ok_or(Error::Overflow)?)?;
+                if !consolidated_flag {
+                    self.env().emit_event(Transferred { from_acc, to_acc: fee_acc, amount_val: fee_val });
+                }
+            }
+
+            let creator_acc = self.creator_treasury_opt.filter(|_| creator_val != 0);
+            if let Some(treasury_acc) = creator_acc {
+                let treasury_bal = self.balances.get(&treasury_acc).unwrap_or(0);
+                self.write_balance(treasury_acc, treasury_bal.checked_add(creator_val).ok_or(Error::Overflow)?)?;
+                if !consolidated_flag {
+                    self.env().emit_event(CreatorFeePaid { from_acc, treasury_acc, amount_val: creator_val });
+                }
+            }
+
+            if consolidated_flag {
+                self.env().emit_event(TransferredWithFee {
+                    from_acc,
+                    to_acc,
+                    amount_val: net_val,
+                    fee_val,
+                    fee_acc,
+                    burn_val,
+                    creator_val,
+                    creator_acc,
+                });
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn mint_and_transfer_works() {
+            let mut c = Moo::new();
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.total_supply(), 0);
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint(100).is_ok());
+            assert_eq!(c.total_supply(), 100);
+            assert_eq!(c.balance_of(accounts.bob), 100);
+            assert!(c.transfer(accounts.charlie, 40).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 60);
+            assert_eq!(c.balance_of(accounts.charlie), 40);
+        }
+
+        #[ink::test]
+        fn pause_blocks_actions() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_pause(true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(matches!(c.mint(1), Err(Error::Paused)));
+        }
+
+        #[ink::test]
+        fn set_minter_requires_owner() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_minter(accounts.charlie, true), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn admin_manages_roles() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.has_role(ROLE_ADMIN, accounts.alice));
+            assert!(c.grant_role(ROLE_PAUSER, accounts.django).is_ok());
+            assert!(c.grant_role(ROLE_BURNER, accounts.eve).is_ok());
+            assert_eq!(c.grant_role(9, accounts.eve), Err(Error::InvalidRole));
+
+            assert!(c.grant_role(ROLE_MINTER, accounts.bob).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint(30).is_ok());
+            assert!(c.approve(accounts.eve, 10).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.burn_from(accounts.bob, 10).is_ok());
+            assert_eq!(c.total_supply(), 20);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(c.grant_role(ROLE_MINTER, accounts.django), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.revoke_role(ROLE_MINTER, accounts.bob).is_ok());
+            assert!(!c.has_role(ROLE_MINTER, accounts.bob));
+        }
+
+        #[ink::test]
+        fn guardian_can_pause_but_not_unpause() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_guardian(accounts.django, true).is_ok());
+            assert!(c.is_guardian(accounts.django));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(c.emergency_pause().is_ok());
+            assert_eq!(c.set_pause(false), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.emergency_pause(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn rescue_own_token_from_contract() {
+            let mut c = Moo::new();
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint(50).is_ok());
+            assert!(c.transfer(accounts.frank, 20).is_ok());
+            assert_eq!(c.rescue_token(accounts.frank, accounts.charlie, 20), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                c.rescue_token(accounts.frank, accounts.charlie, 21),
+                Err(Error::InsufficientBalance)
+            );
+            assert!(c.rescue_token(accounts.frank, accounts.charlie, 20).is_ok());
+            assert_eq!(c.balance_of(accounts.frank), 0);
+            assert_eq!(c.balance_of(accounts.charlie), 20);
+        }
+
+        #[ink::test]
+        fn set_oracle_requires_owner() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.oracle(), None);
+            assert!(c.set_oracle(Some(accounts.django)).is_ok());
+            assert_eq!(c.oracle(), Some(accounts.django));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_oracle(None), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn capabilities_reflect_configuration() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.version(), 1);
+            assert_eq!(c.capabilities() & CAP_GUARDIANS, CAP_GUARDIANS);
+            assert_eq!(c.capabilities() & (CAP_METADATA | CAP_ORACLE), 0);
+            assert!(c.set_oracle(Some(accounts.django)).is_ok());
+            assert_eq!(c.capabilities() & CAP_ORACLE, CAP_ORACLE);
+        }
+
+        #[ink::test]
+        fn mint_with_sig_rejects_bad_signature() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.mint_with_sig(accounts.bob, 10, 0, [0u8; 65]), Err(Error::InvalidSignature));
+            assert_eq!(c.total_supply(), 0);
+            assert!(!c.is_mint_nonce_used(accounts.alice, 0));
+        }
+
+        #[ink::test]
+        fn staking_accrues_and_claims_rewards() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert!(c.set_minter(accounts.frank, true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert!(c.set_reward_rate(REWARD_SCALE / 100).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.stake(1_000).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 0);
+            assert_eq!(c.staked_of(accounts.bob), 1_000);
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.pending_rewards(accounts.bob), 20);
+
+            // restaking settles first, so the next block accrues on the new stake only
+            assert!(c.unstake(500).is_ok());
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.pending_rewards(accounts.bob), 25);
+
+            assert!(c.claim_rewards().is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 525);
+            assert_eq!(c.pending_rewards(accounts.bob), 0);
+            assert_eq!(c.unstake(501), Err(Error::InsufficientStake));
+        }
+
+        #[ink::test]
+        fn to_display_splits_by_decimals() {
+            let c = Moo::new();
+            assert_eq!(c.decimals(), 12);
+            assert_eq!(c.to_display(1_500_000_000_000), (1, 500_000_000_000));
+            let c = Moo::new_with_decimals(0);
+            assert_eq!(c.decimals(), 0);
+            assert_eq!(c.to_display(42), (42, 0));
+            let c = Moo::new_with_decimals(18);
+            assert_eq!(c.decimals(), 18);
+            assert_eq!(c.to_display(2_000_000_000_000_000_123), (2, 123));
+        }
+
+        #[ink::test]
+        fn burn_with_memo_bounds_memo() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint(10).is_ok());
+            assert_eq!(c.burn_with_memo(1, ink::prelude::vec![0u8; 129]), Err(Error::MemoTooLong));
+            assert!(c.burn_with_memo(4, b"order-7".to_vec()).is_ok());
+            assert_eq!(c.total_supply(), 6);
+        }
+
+        #[ink::test]
+        fn self_transfer_follows_policy() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint(10).is_ok());
+            assert_eq!(c.transfer(accounts.alice, 5), Err(Error::SameAccount));
+            assert!(c.set_allow_self_transfer(true).is_ok());
+            assert!(c.transfer(accounts.alice, 5).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 10);
+            assert_eq!(c.transfer(accounts.alice, 11), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn every_error_code_has_a_message() {
+            let c = Moo::new();
+            let mut known_cnt = 0;
+            for code_u8 in 0..=u8::MAX {
+                if let Ok(err) = Error::decode(&mut &[code_u8][..]) {
+                    assert!(!err.message().is_empty());
+                    assert_eq!(c.error_message(code_u8), err.message());
+                    known_cnt += 1;
+                } else {
+                    assert_eq!(c.error_message(code_u8), "unknown error");
+                }
+            }
+            assert!(known_cnt > 0);
+            assert_eq!(c.error_message(1), "insufficient balance");
+        }
+
+        #[ink::test]
+        fn constructor_emits_genesis_events() {
+            let c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.has_role(ROLE_MINTER, accounts.alice));
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+            let d = Moo::new();
+            assert!(!d.has_role(ROLE_MINTER, accounts.alice));
+        }
+
+        #[ink::test]
+        fn pause_reason_tracks_pause_state() {
+            let mut c = Moo::new();
+            assert_eq!(c.pause_reason(), None);
+            assert!(c.set_pause_with_reason(true, PAUSE_REASON_MIGRATION).is_ok());
+            assert_eq!(c.pause_reason(), Some(PAUSE_REASON_MIGRATION));
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(c.pause_reason(), Some(PAUSE_REASON_UNSPECIFIED));
+            assert!(c.set_pause(false).is_ok());
+            assert_eq!(c.pause_reason(), None);
+        }
+
+        #[ink::test]
+        fn dividends_pay_pro_rata_once() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<E>();
+            ink::env::test::set_callee::<E>(accounts.frank);
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 25).is_ok());
+
+            ink::env::test::set_account_balance::<E>(accounts.frank, 1_000);
+            ink::env::test::set_value_transferred::<E>(1_000);
+            assert_eq!(c.deposit_dividend(), Ok(1));
+
+            // moves after the snapshot don't change the epoch's shares
+            assert!(c.transfer(accounts.bob, 75).is_ok());
+            assert_eq!(c.balance_of_at(accounts.bob, 1), Ok(25));
+            assert_eq!(c.balance_of_at(accounts.bob, 2), Err(Error::InvalidSnapshot));
+
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.claim_dividend(1), Ok(250));
+            assert_eq!(c.claim_dividend(1), Err(Error::AlreadyClaimed));
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert_eq!(c.claim_dividend(1), Ok(750));
+        }
+
+        #[ink::test]
+        fn burn_to_dead_keeps_supply() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.burn(10).is_ok());
+            assert_eq!(c.total_supply(), 90);
+
+            assert!(c.set_burn_mode(true, accounts.django).is_ok());
+            assert_eq!(c.burn_mode(), (true, accounts.django));
+            assert!(c.burn(30).is_ok());
+            assert_eq!(c.total_supply(), 90);
+            assert_eq!(c.balance_of(accounts.django), 30);
+            assert_eq!(c.balance_of(accounts.alice), 60);
+        }
+
+        #[ink::test]
+        fn balance_and_allowance_reads_both() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(50).is_ok());
+            assert!(c.approve(accounts.bob, 20).is_ok());
+            assert_eq!(c.balance_and_allowance(accounts.alice, accounts.bob), (50, 20));
+            assert_eq!(c.balance_and_allowance(accounts.bob, accounts.alice), (0, 0));
+        }
+
+        #[ink::test]
+        fn batch_transfer_respects_max_batch_size() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert_eq!(c.max_batch_size(), DEFAULT_MAX_BATCH_SIZE);
+            assert!(c.set_max_batch_size(2).is_ok());
+            let batch_vec = ink::prelude::vec![(accounts.bob, 10), (accounts.charlie, 20), (accounts.django, 30)];
+            assert_eq!(c.batch_transfer(batch_vec), Err(Error::Overflow));
+            assert!(c.batch_transfer(ink::prelude::vec![(accounts.bob, 10), (accounts.charlie, 20)]).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 20);
+            assert_eq!(c.balance_of(accounts.alice), 70);
+        }
+
+        #[ink::test]
+        fn total_supply_at_matches_snapshot() {
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.mint(100).is_ok());
+            assert_eq!(c.snapshot(), Ok(1));
+            assert!(c.mint(50).is_ok());
+            assert!(c.burn(30).is_ok());
+            assert_eq!(c.snapshot(), Ok(2));
+            assert_eq!(c.total_supply_at(1), Ok(100));
+            assert_eq!(c.total_supply_at(2), Ok(120));
+            assert_eq!(c.total_supply_at(0), Err(Error::InvalidSnapshot));
+            assert_eq!(c.total_supply_at(3), Err(Error::InvalidSnapshot));
+        }
+
+        #[ink::test]
+        fn pause_with_sig_rejects_bad_signature() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.pause_with_sig([0u8; 65], 7), Err(Error::InvalidSignature));
+            assert_eq!(c.pause_reason(), None);
+            assert!(!c.is_pause_nonce_used(accounts.alice, 7));
+        }
+
+        #[ink::test]
+        fn last_activity_tracks_balance_changes() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.last_activity_of(accounts.bob), 0);
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(10).is_ok());
+            let minted_at = c.last_activity_of(accounts.alice);
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert!(c.transfer(accounts.bob, 4).is_ok());
+            assert!(c.last_activity_of(accounts.alice) > minted_at);
+            assert_eq!(c.last_activity_of(accounts.bob), c.last_activity_of(accounts.alice));
+            assert_eq!(c.last_activity_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn batch_transfer_from_spends_each_allowance() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 40).is_ok());
+            assert!(c.approve(accounts.eve, 30).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.approve(accounts.eve, 20).is_ok());
+
+            ink::env::test::set_caller::<E>(accounts.eve);
+            let legs_vec = ink::prelude::vec![
+                (accounts.alice, accounts.charlie, 30),
+                (accounts.bob, accounts.charlie, 15),
+            ];
+            assert!(c.batch_transfer_from(legs_vec).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 45);
+            assert_eq!(c.allowance(accounts.alice, accounts.eve), 0);
+            assert_eq!(c.allowance(accounts.bob, accounts.eve), 5);
+        }
+
+        #[ink::test]
+        fn circulating_supply_skips_excluded() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 30).is_ok());
+            assert!(c.transfer(accounts.charlie, 20).is_ok());
+            assert_eq!(c.circulating_supply(), 50);
+
+            assert!(c.set_excluded(accounts.bob, true).is_ok());
+            assert!(c.set_excluded(accounts.bob, true).is_ok());
+            assert_eq!(c.excluded_accounts(), ink::prelude::vec![accounts.bob]);
+            assert_eq!(c.circulating_supply(), 20);
+            assert!(c.set_excluded(accounts.bob, false).is_ok());
+            assert_eq!(c.circulating_supply(), 50);
+        }
+
+        #[ink::test]
+        fn approve_same_value_emits_nothing() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.approve(accounts.bob, 0).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt);
+            assert!(c.approve(accounts.bob, 10).is_ok());
+            assert!(c.approve(accounts.bob, 10).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 1);
+            assert_eq!(c.approve(accounts.bob, 11), Err(Error::AllowanceRace));
+        }
+
+        #[ink::test]
+        fn min_transfer_amount_blocks_dust() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.set_min_transfer_amount(10).is_ok());
+            assert_eq!(c.min_transfer_amount(), 10);
+            assert_eq!(c.transfer(accounts.bob, 9), Err(Error::BelowMinimum));
+            assert!(c.transfer(accounts.bob, 10).is_ok());
+
+            assert!(c.approve(accounts.charlie, 50).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.transfer_from(accounts.alice, accounts.django, 9), Err(Error::BelowMinimum));
+            assert!(c.transfer_from(accounts.alice, accounts.django, 10).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.burn(1).is_ok());
+        }
+
+        #[ink::test]
+        fn supply_floor_limits_burns() {
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.mint(100).is_ok());
+            assert!(c.set_supply_floor(60).is_ok());
+            assert_eq!(c.supply_floor(), 60);
+            assert_eq!(c.burn(41), Err(Error::SupplyFloor));
+            assert!(c.burn(40).is_ok());
+            assert_eq!(c.total_supply(), 60);
+            assert_eq!(c.burn(1), Err(Error::SupplyFloor));
+        }
+
+        #[ink::test]
+        fn transfer_from_with_permit_rejects_bad_permits() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(50).is_ok());
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                c.transfer_from_with_permit(accounts.alice, accounts.bob, 10, 0, [0u8; 65]),
+                Err(Error::PermitExpired)
+            );
+            assert_eq!(
+                c.transfer_from_with_permit(accounts.alice, accounts.bob, 10, u64::MAX, [0u8; 65]),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(c.balance_of(accounts.bob), 0);
+            assert_eq!(c.permit_nonce(accounts.alice), 0);
+            assert_eq!(c.capabilities() & CAP_PERMIT, CAP_PERMIT);
+        }
+
+        #[ink::test]
+        fn max_mint_per_call_bounds_mints() {
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.set_max_mint_per_call(100).is_ok());
+            assert_eq!(c.max_mint_per_call(), 100);
+            assert_eq!(c.mint(101), Err(Error::MintPerCallExceeded));
+            assert!(c.mint(100).is_ok());
+            assert!(c.set_max_mint_per_call(0).is_ok());
+            assert!(c.mint(1_000).is_ok());
+            assert_eq!(c.total_supply(), 2_100);
+        }
+
+        #[ink::test]
+        fn blacklisted_spender_cannot_pull() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.approve(accounts.bob, 50).is_ok());
+            assert!(c.blacklist_spender(accounts.bob, true).is_ok());
+            assert!(c.is_spender_blacklisted(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.transfer_from(accounts.alice, accounts.bob, 10), Err(Error::SpenderBlacklisted));
+            assert_eq!(c.allowance(accounts.alice, accounts.bob), 50);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.blacklist_spender(accounts.bob, false).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer_from(accounts.alice, accounts.bob, 10).is_ok());
+        }
+
+        #[ink::test]
+        fn holders_track_nonzero_balances() {
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 10).is_ok());
+            assert!(c.transfer(accounts.charlie, 10).is_ok());
+            assert_eq!(c.holder_count(), 3);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 10).is_ok());
+            assert_eq!(c.holder_count(), 2);
+            let holders_vec = c.holders(0, 10);
+            assert!(holders_vec.contains(&accounts.alice) && holders_vec.contains(&accounts.charlie));
+            assert_eq!(c.holders(1, 10).len(), 1);
+        }
+
+        #[ink::test]
+        fn receive_cooldown_blocks_quick_resend() {
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_receive_cooldown(2, true).is_ok());
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 10).is_ok());
+            let unlock_block = c.receive_unlock_block_of(accounts.bob);
+            assert!(unlock_block > 0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 5), Err(Error::Cooldown));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert!(c.transfer(accounts.charlie, 5).is_ok());
+        }
+
+        #[ink::test]
+        fn transfer_fee_skips_exempt_accounts() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(1_000).is_ok());
+            assert_eq!(c.set_transfer_fee(1_001, accounts.eve), Err(Error::InvalidFee));
+            assert!(c.set_transfer_fee(100, accounts.eve).is_ok());
+            assert!(c.transfer(accounts.bob, 500).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 495);
+            assert_eq!(c.balance_of(accounts.eve), 5);
+
+            assert!(c.set_fee_exempt(accounts.charlie, true).is_ok());
+            assert!(c.is_fee_exempt(accounts.charlie));
+            assert!(c.transfer(accounts.charlie, 100).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 100);
+            assert_eq!(c.balance_of(accounts.eve), 5);
+            assert_eq!(c.total_supply(), 1_000);
+        }
+
+        #[ink::test]
+        fn transfer_and_call_bounds_data() {
+            // the receiver callback itself needs a deployed contract (e2e); off-chain we
+            // cover the length bound and the plain-account path
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(100).is_ok());
+            let too_long = ink::prelude::vec![7u8; MAX_CALL_DATA_LEN + 1];
+            assert_eq!(c.transfer_and_call(accounts.bob, 10, too_long), Err(Error::CallDataTooLong));
+            let data_vec = ink::prelude::vec![7u8; MAX_CALL_DATA_LEN];
+            assert!(c.transfer_and_call(accounts.bob, 10, data_vec).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn trading_gate_opens_once() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(!c.trading_enabled());
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 40).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 10), Err(Error::TradingDisabled));
+            assert_eq!(c.enable_trading(), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.enable_trading().is_ok());
+            assert!(c.trading_enabled());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 10).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 10);
+        }
+
+        #[ink::test]
+        fn allowance_shortfall_reports_top_up() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.allowance_shortfall(accounts.alice, accounts.bob, 50), 50);
+            assert!(c.approve(accounts.bob, 30).is_ok());
+            assert_eq!(c.allowance_shortfall(accounts.alice, accounts.bob, 50), 20);
+            assert_eq!(c.allowance_shortfall(accounts.alice, accounts.bob, 30), 0);
+            assert_eq!(c.allowance_shortfall(accounts.alice, accounts.bob, 10), 0);
+        }
+
+        #[ink::test]
+        fn batch_approve_applies_safe_or_forced() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.batch_approve(ink::prelude::vec![(accounts.bob, 10), (accounts.charlie, 20)], false).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 2);
+            assert_eq!(c.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(c.allowance(accounts.alice, accounts.charlie), 20);
+            assert_eq!(
+                c.batch_approve(ink::prelude::vec![(accounts.django, 5), (accounts.bob, 15)], false),
+                Err(Error::AllowanceRace)
+            );
+            assert!(c.batch_approve(ink::prelude::vec![(accounts.bob, 15), (accounts.charlie, 0)], true).is_ok());
+            assert_eq!(c.allowance(accounts.alice, accounts.bob), 15);
+            assert_eq!(c.allowance(accounts.alice, accounts.charlie), 0);
+            assert!(c.set_max_batch_size(1).is_ok());
+            assert_eq!(
+                c.batch_approve(ink::prelude::vec![(accounts.eve, 1), (accounts.frank, 1)], true),
+                Err(Error::Overflow)
+            );
+        }
+
+        #[ink::test]
+        fn balance_cap_limits_receivers_only() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.enable_trading().is_ok());
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.set_max_balance_per_account(Some(100)).is_ok());
+            assert_eq!(c.max_balance_per_account(), Some(100));
+            assert_eq!(c.mint(1), Err(Error::BalanceCapExceeded));
+            assert!(c.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(c.transfer(accounts.bob, 1), Err(Error::BalanceCapExceeded));
+            assert!(c.set_balance_cap_exempt(accounts.bob, true).is_ok());
+            assert!(c.transfer(accounts.bob, 50).is_ok());
+            assert!(c.set_balance_cap_exempt(accounts.alice, true).is_ok());
+            assert!(c.mint(1).is_ok());
+            // sending out and burning are never capped
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 100).is_ok());
+            assert!(c.burn(10).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn era_cap_resets_each_era() {
+            let mut c = Moo::new_with_minter(true);
+            assert_eq!(c.era_remaining(), Balance::MAX);
+            assert!(c.set_era_cap(10, 100).is_ok());
+            assert_eq!(c.current_era(), 0);
+            assert!(c.mint(60).is_ok());
+            assert_eq!(c.era_remaining(), 40);
+            assert_eq!(c.mint(41), Err(Error::EraCapExceeded));
+            assert!(c.mint(40).is_ok());
+            for _ in 0..10 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(c.current_era(), 1);
+            assert_eq!(c.era_remaining(), 100);
+            assert!(c.mint(100).is_ok());
+            assert_eq!(c.era_remaining(), 0);
+            assert_eq!(c.total_supply(), 200);
+        }
+
+        #[ink::test]
+        fn recover_ownership_requires_threshold() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.recover_ownership(accounts.bob, ink::prelude::vec![]), Err(Error::RecoveryNotConfigured));
+            assert!(c.set_guardian(accounts.charlie, true).is_ok());
+            assert!(c.set_recovery_threshold(2).is_ok());
+            assert_eq!(c.recovery_config(), (2, 0));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_recovery_threshold(0), Err(Error::NotOwner));
+            assert_eq!(c.recover_ownership(accounts.bob, ink::prelude::vec![]), Err(Error::RecoveryThreshold));
+            assert_eq!(
+                c.recover_ownership(accounts.bob, ink::prelude::vec![[0u8; 65]]),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(
+                c.recover_ownership(accounts.bob, ink::prelude::vec![[0u8; 65]; MAX_RECOVERY_SIGS + 1]),
+                Err(Error::Overflow)
+            );
+            assert!(c.has_role(ROLE_ADMIN, accounts.alice));
+            assert_eq!(c.recovery_config(), (2, 0));
+        }
+
+        #[ink::test]
+        fn treasury_constructor_seeds_supply() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let c = Moo::new_with_treasury(1_000, accounts.django).unwrap();
+            assert_eq!(c.total_supply(), 1_000);
+            assert_eq!(c.balance_of(accounts.django), 1_000);
+            assert!(c.has_role(ROLE_ADMIN, accounts.alice));
+            assert!(!c.has_role(ROLE_MINTER, accounts.alice));
+            let empty = Moo::new_with_treasury(0, accounts.django).unwrap();
+            assert_eq!(empty.total_supply(), 0);
+            assert_eq!(empty.holder_count(), 0);
+        }
+
+        #[ink::test]
+        fn approve_checked_is_compare_and_set() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.approve_checked(accounts.bob, 0, 10).is_ok());
+            assert_eq!(c.approve_checked(accounts.bob, 0, 20), Err(Error::AllowanceRace));
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.approve_checked(accounts.bob, 10, 20).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 1);
+            assert_eq!(c.allowance(accounts.alice, accounts.bob), 20);
+        }
+
+        #[ink::test]
+        fn mint_to_credits_recipient() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint_to(accounts.bob, 25).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 25);
+            assert_eq!(c.balance_of(accounts.alice), 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.mint_to(accounts.bob, 1), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn role_config_tracks_role_counts() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.grant_role(ROLE_PAUSER, accounts.bob).is_ok());
+            assert!(c.grant_role(ROLE_PAUSER, accounts.bob).is_ok());
+            assert!(c.set_minter(accounts.charlie, true).is_ok());
+            assert!(c.set_minter(accounts.alice, false).is_ok());
+            assert!(c.set_max_mint_per_call(50).is_ok());
+            let config = c.role_config();
+            assert_eq!(config.owner, accounts.alice);
+            assert_eq!(config.admin_cnt, 1);
+            assert_eq!(config.minter_cnt, 1);
+            assert_eq!(config.pauser_cnt, 1);
+            assert_eq!(config.burner_cnt, 0);
+            assert!(!config.paused);
+            assert_eq!(config.max_mint_per_call, 50);
+            assert_eq!(config.schema_ver, 1);
+        }
+
+        #[ink::test]
+        fn spend_limit_caps_lifetime_pulls() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.approve_with_limit(accounts.eve, 30).is_ok());
+            assert_eq!(c.spend_limit_of(accounts.alice, accounts.eve), Some(30));
+            ink::env::test::set_caller::<E>(accounts.eve);
+            assert!(c.transfer_from(accounts.alice, accounts.bob, 20).is_ok());
+            assert_eq!(c.spent_of(accounts.alice, accounts.eve), 20);
+            // topping up the allowance doesn't lift the cap
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.increase_allowance(accounts.eve, 50).is_ok());
+            ink::env::test::set_caller::<E>(accounts.eve);
+            assert_eq!(c.transfer_from(accounts.alice, accounts.bob, 11), Err(Error::SpendLimitExceeded));
+            assert!(c.transfer_from(accounts.alice, accounts.bob, 10).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 30);
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.approve_with_limit(accounts.eve, 0).is_ok());
+            assert_eq!(c.spend_limit_of(accounts.alice, accounts.eve), None);
+            assert_eq!(c.spent_of(accounts.alice, accounts.eve), 0);
+            assert_eq!(c.allowance(accounts.alice, accounts.eve), 0);
+        }
+
+        #[ink::test]
+        fn staking_totals_track_stakers() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 40).is_ok());
+            assert!(c.stake(30).is_ok());
+            assert!(c.stake(10).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.stake(20).is_ok());
+            assert_eq!(c.total_staked(), 60);
+            assert_eq!(c.staker_count(), 2);
+            assert!(c.unstake(20).is_ok());
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.unstake(15).is_ok());
+            assert_eq!(c.total_staked(), 25);
+            assert_eq!(c.staker_count(), 1);
+        }
+
+        #[ink::test]
+        fn unstake_ignores_balance_cap() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(140).is_ok());
+            assert!(c.transfer(accounts.bob, 40).is_ok());
+            assert!(c.stake(40).is_ok());
+            assert!(c.set_max_balance_per_account(Some(100)).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.transfer(accounts.alice, 40).is_ok());
+            // sitting at the cap, the staker still gets the whole principal back
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.unstake(40).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 140);
+            assert_eq!(c.staked_of(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn unstaked_principal_is_not_receive_locked() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.stake(60).is_ok());
+            assert!(c.set_receive_cooldown(10, false).is_ok());
+            assert!(c.unstake(60).is_ok());
+            assert_eq!(c.receive_locked_of(accounts.alice), 0);
+            assert!(c.transfer(accounts.bob, 100).is_ok());
+        }
+
+        #[ink::test]
+        fn consolidated_fee_event_replaces_pair() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.set_transfer_fee(100, accounts.eve).is_ok());
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 2);
+            assert!(c.set_consolidated_fee_event(true).is_ok());
+            assert!(c.consolidated_fee_event());
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 1);
+            assert_eq!(c.balance_of(accounts.bob), 198);
+            assert_eq!(c.balance_of(accounts.eve), 2);
+        }
+
+        #[ink::test]
+        fn reserve_checks_compare_native_balance_to_supply() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            let contract_acc = ink::env::test::callee::<E>();
+            assert_eq!(c.reserve_ratio(), 10_000);
+            assert!(c.mint(100).is_ok());
+            ink::env::test::set_account_balance::<E>(contract_acc, 0);
+            assert_eq!(c.native_reserve(), 0);
+            assert!(!c.is_fully_backed());
+            assert_eq!(c.reserve_ratio(), 0);
+            ink::env::test::set_account_balance::<E>(contract_acc, 1_000_000_000_000);
+            assert!(c.is_fully_backed());
+            assert!(c.reserve_ratio() > 10_000);
+        }
+
+        #[ink::test]
+        fn transfer_burn_shrinks_supply() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(1_000).is_ok());
+            assert_eq!(c.set_burn_bps(MAX_BURN_BPS + 1), Err(Error::InvalidFee));
+            assert!(c.set_burn_bps(200).is_ok());
+            assert_eq!(c.burn_bps(), 200);
+            assert!(c.set_transfer_fee(100, accounts.eve).is_ok());
+            assert!(c.transfer(accounts.bob, 500).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 485);
+            assert_eq!(c.balance_of(accounts.eve), 5);
+            assert_eq!(c.total_supply(), 990);
+            assert!(c.set_fee_exempt(accounts.charlie, true).is_ok());
+            assert!(c.transfer(accounts.charlie, 100).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 100);
+            assert_eq!(c.total_supply(), 990);
+        }
+
+        #[ink::test]
+        fn renounce_freezes_configuration() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(!c.is_renounced());
+            assert!(c.grant_role(ROLE_ADMIN, accounts.bob).is_ok());
+            assert_eq!(c.renounce_ownership(), Err(Error::OtherAdmins));
+            assert!(c.revoke_role(ROLE_ADMIN, accounts.bob).is_ok());
+            assert!(c.renounce_ownership().is_ok());
+            assert!(c.is_renounced());
+            assert!(c.role_config().renounced);
+            assert_eq!(c.set_max_mint_per_call(1), Err(Error::NotOwner));
+            assert_eq!(c.renounce_ownership(), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn role_batches_apply_to_every_account() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let minters_vec = ink::prelude::vec![accounts.bob, accounts.charlie, accounts.django];
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.grant_role_batch(ROLE_MINTER, minters_vec.clone()).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 3);
+            assert_eq!(c.role_count(ROLE_MINTER), 3);
+            assert!(c.revoke_role_batch(ROLE_MINTER, ink::prelude::vec![accounts.bob, accounts.django]).is_ok());
+            assert!(c.has_role(ROLE_MINTER, accounts.charlie));
+            assert_eq!(c.role_count(ROLE_MINTER), 1);
+            assert_eq!(c.grant_role_batch(9, minters_vec.clone()), Err(Error::InvalidRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.grant_role_batch(ROLE_MINTER, minters_vec), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn message_selectors_cover_the_api() {
+            let c = Moo::new();
+            let selectors_vec = c.message_selectors();
+            assert!(selectors_vec.contains(&ink::selector_bytes!("transfer")));
+            assert!(selectors_vec.contains(&ink::selector_bytes!("message_selectors")));
+            assert!(c.supports_selector(ink::selector_bytes!("balance_of")));
+            assert!(!c.supports_selector(ink::selector_bytes!("no_such_message")));
+        }
+
+        #[ink::test]
+        fn active_allowance_cap_counts_nonzero_approvals() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.set_max_active_allowances(Some(2)).is_ok());
+            assert!(c.approve(accounts.bob, 10).is_ok());
+            assert!(c.approve(accounts.charlie, 10).is_ok());
+            assert_eq!(c.active_allowance_count(accounts.alice), 2);
+            assert_eq!(c.approve(accounts.django, 10), Err(Error::TooManyAllowances));
+            assert!(c.increase_allowance(accounts.bob, 5).is_ok());
+            // spending an allowance to zero frees its slot
+            ink::env::test::set_caller::<E>(accounts.charlie);
+            assert!(c.transfer_from(accounts.alice, accounts.eve, 10).is_ok());
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert_eq!(c.active_allowance_count(accounts.alice), 1);
+            assert!(c.approve(accounts.django, 10).is_ok());
+            assert!(c.approve(accounts.bob, 0).is_ok());
+            assert_eq!(c.active_allowance_count(accounts.alice), 1);
+        }
+
+        #[ink::test]
+        fn creator_fee_funds_collection_treasury() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(1_000).is_ok());
+            // the default fee recipient (the owner) is fee-exempt; move it off the sender
+            assert!(c.set_transfer_fee(0, accounts.eve).is_ok());
+            assert_eq!(c.set_creator_fee(MAX_TRANSFER_FEE_BPS + 1, Some(accounts.frank)), Err(Error::InvalidFee));
+            assert!(c.set_creator_fee(200, Some(accounts.frank)).is_ok());
+            assert_eq!(c.creator_fee(), (200, Some(accounts.frank)));
+            assert!(c.transfer(accounts.bob, 500).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 490);
+            assert_eq!(c.balance_of(accounts.frank), 10);
+            assert!(c.set_fee_exempt(accounts.charlie, true).is_ok());
+            assert!(c.transfer(accounts.charlie, 100).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 100);
+            assert_eq!(c.total_supply(), 1_000);
+        }
+
+        #[ink::test]
+        fn new_with_minters_grants_each_minter() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let c = Moo::new_with_minters(ink::prelude::vec![accounts.bob, accounts.charlie]).unwrap();
+            assert!(c.has_role(ROLE_MINTER, accounts.bob));
+            assert!(c.has_role(ROLE_MINTER, accounts.charlie));
+            assert!(!c.has_role(ROLE_MINTER, accounts.alice));
+            assert_eq!(c.role_count(ROLE_MINTER), 2);
+            let too_many = ink::prelude::vec![accounts.bob; MAX_INITIAL_MINTERS + 1];
+            assert!(matches!(Moo::new_with_minters(too_many), Err(Error::Overflow)));
+        }
+
+        #[ink::test]
+        fn reversible_transfer_can_be_clawed_back_in_window() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert_eq!(c.transfer_reversible(accounts.bob, 10), Err(Error::NotReversible));
+            assert!(c.set_reversible_window(2).is_ok());
+            let sent_at = ink::env::block_number::<E>();
+            assert!(c.transfer_reversible(accounts.bob, 30).is_ok());
+            assert!(c.transfer_reversible(accounts.charlie, 20).is_ok());
+            assert_eq!(c.reversible_of(accounts.alice, accounts.bob, sent_at), 30);
+            assert!(c.reverse_transfer(accounts.bob, sent_at).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 0);
+            assert_eq!(c.balance_of(accounts.alice), 80);
+            assert_eq!(c.reverse_transfer(accounts.bob, sent_at), Err(Error::NotReversible));
+            // spent funds can't be pulled back
+            ink::env::test::set_caller::<E>(accounts.charlie);
+            assert!(c.transfer(accounts.django, 15).is_ok());
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert_eq!(c.reverse_transfer(accounts.charlie, sent_at), Err(Error::NotReversible));
+            for _ in 0..3 {
+                ink::env::test::advance_block::<E>();
+            }
+            assert_eq!(c.reverse_transfer(accounts.charlie, sent_at), Err(Error::NotReversible));
+        }
+
+        #[ink::test]
+        fn roles_of_reports_bitmask() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.roles_of(accounts.bob), 0);
+            assert!(c.grant_role(ROLE_MINTER, accounts.bob).is_ok());
+            assert!(c.grant_role(ROLE_BURNER, accounts.bob).is_ok());
+            assert_eq!(c.roles_of(accounts.bob), 0b1010);
+            assert!(c.revoke_role(ROLE_MINTER, accounts.bob).is_ok());
+            assert_eq!(c.roles_of(accounts.bob), 0b1000);
+            assert_eq!(c.roles_of(accounts.alice) & 1, 1);
+        }
+
+        #[ink::test]
+        fn large_stakes_settle_and_unstake_survives_overflow() {
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // the full-width product of these overflows u128; the scaled result doesn't
+            let (stake_val, acc_val) = (10u128.pow(27), 10u128.pow(18));
+            assert_eq!(Moo::scale_reward(stake_val, acc_val), Ok(10u128.pow(33)));
+            assert_eq!(Moo::scale_reward(1_999_999_999_999, 3 * REWARD_SCALE + 1), Ok(5_999_999_999_998));
+
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_minter(ink::env::test::callee::<ink::env::DefaultEnvironment>(), true).is_ok());
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.stake(1_000).is_ok());
+            assert!(c.set_reward_rate(Balance::MAX / 2).is_ok());
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            // the accumulator itself overflows now; rewards are lost, principal is not
+            assert_eq!(c.claim_rewards(), Err(Error::Overflow));
+            assert!(c.unstake(1_000).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 1_000);
+            assert_eq!(c.staked_of(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn dividends_exclude_staked_supply() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<E>();
+            ink::env::test::set_callee::<E>(accounts.frank);
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 20).is_ok());
+            assert!(c.stake(30).is_ok());
+
+            ink::env::test::set_account_balance::<E>(accounts.frank, 700);
+            ink::env::test::set_value_transferred::<E>(700);
+            assert_eq!(c.deposit_dividend(), Ok(1));
+            assert_eq!(c.dividend_of(1), Some((700, 70)));
+
+            // the two holders drain the whole pool; nothing is stranded for the stakes
+            assert_eq!(c.claim_dividend(1), Ok(500));
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.claim_dividend(1), Ok(200));
+        }
+
+        #[ink::test]
+        fn receive_cooldown_locks_only_received_amounts() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_receive_cooldown(3, true).is_ok());
+            assert!(c.mint(100).is_ok());
+            assert!(c.transfer(accounts.bob, 10).is_ok());
+            assert_eq!(c.receive_locked_of(accounts.bob), 10);
+            // unsolicited dust every block only ever extends the newest slot
+            for _ in 0..3 {
+                ink::env::test::advance_block::<E>();
+                assert!(c.transfer(accounts.bob, 1).is_ok());
+            }
+            // the first receipt unlocked on schedule; only the dust is still locked
+            assert_eq!(c.receive_locked_of(accounts.bob), 3);
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.transfer(accounts.django, 10).is_ok());
+            assert_eq!(c.transfer(accounts.django, 1), Err(Error::Cooldown));
+            assert_eq!(c.transfer(accounts.django, 4), Err(Error::InsufficientBalance));
+
+            // fee-exempt accounts are never locked, and turning the feature off frees everyone
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.set_fee_exempt(accounts.eve, true).is_ok());
+            assert!(c.transfer(accounts.eve, 5).is_ok());
+            assert_eq!(c.receive_locked_of(accounts.eve), 0);
+            assert!(c.set_receive_cooldown(0, true).is_ok());
+            assert_eq!(c.receive_locked_of(accounts.bob), 0);
+            assert_eq!(c.receive_unlock_block_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn reversal_never_reaches_recipients_other_funds() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            assert!(c.enable_trading().is_ok());
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(200).is_ok());
+            assert!(c.set_reversible_window(5).is_ok());
+            assert!(c.transfer(accounts.bob, 50).is_ok());
+            let sent_at = ink::env::block_number::<E>();
+            assert!(c.transfer_reversible(accounts.bob, 30).is_ok());
+            // bob spends exactly the received amount; his earlier 50 stays his
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 30).is_ok());
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert_eq!(c.reversible_of(accounts.alice, accounts.bob, sent_at), 0);
+            assert_eq!(c.reverse_transfer(accounts.bob, sent_at), Err(Error::NotReversible));
+            assert_eq!(c.balance_of(accounts.bob), 50);
+
+            // reversals are gated like any other move out of the recipient
+            assert!(c.transfer_reversible(accounts.charlie, 10).is_ok());
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(c.reverse_transfer(accounts.charlie, sent_at), Err(Error::Paused));
+            assert!(c.set_pause(false).is_ok());
+            assert!(c.blacklist_spender(accounts.alice, true).is_ok());
+            assert_eq!(c.reverse_transfer(accounts.charlie, sent_at), Err(Error::SpenderBlacklisted));
+            assert!(c.blacklist_spender(accounts.alice, false).is_ok());
+            assert!(c.reverse_transfer(accounts.charlie, sent_at).is_ok());
+            assert_eq!(c.balance_of(accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn burn_from_counts_against_spend_limit() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.mint(100).is_ok());
+            assert!(c.grant_role(ROLE_BURNER, accounts.eve).is_ok());
+            assert!(c.approve_with_limit(accounts.eve, 30).is_ok());
+            assert!(c.increase_allowance(accounts.eve, 50).is_ok());
+            ink::env::test::set_caller::<E>(accounts.eve);
+            assert!(c.burn_from(accounts.alice, 20).is_ok());
+            assert_eq!(c.spent_of(accounts.alice, accounts.eve), 20);
+            assert_eq!(c.burn_from(accounts.alice, 11), Err(Error::SpendLimitExceeded));
+            assert!(c.burn_from(accounts.alice, 10).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 70);
+            assert_eq!(c.allowance(accounts.alice, accounts.eve), 50);
+        }
+
+        #[ink::test]
+        fn era_cap_survives_reconfig_and_counts_rewards() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = Moo::new();
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_minter(ink::env::test::callee::<E>(), true).is_ok());
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.stake(1_000).is_ok());
+            assert!(c.set_era_cap(1_000, 100).is_ok());
+            assert!(c.mint(60).is_ok());
+            // same era: a reconfiguration keeps what was already minted
+            assert!(c.set_era_cap(1_000, 100).is_ok());
+            assert_eq!(c.era_remaining(), 40);
+            // 30 per block on a 1_000 stake
+            assert!(c.set_reward_rate(3 * REWARD_SCALE / 100).is_ok());
+            ink::env::test::advance_block::<E>();
+            ink::env::test::advance_block::<E>();
+            assert_eq!(c.pending_rewards(accounts.alice), 60);
+            assert_eq!(c.claim_rewards(), Err(Error::EraCapExceeded));
+            assert_eq!(c.pending_rewards(accounts.alice), 60);
+            assert!(c.set_era_cap(1_000, 200).is_ok());
+            assert_eq!(c.era_remaining(), 140);
+            assert!(c.claim_rewards().is_ok());
+            assert_eq!(c.era_remaining(), 80);
+            assert_eq!(c.balance_of(accounts.alice), 120);
+        }
+
+        #[ink::test]
+        fn consolidated_fee_event_folds_in_burn_and_creator_fee() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(10_000).is_ok());
+            assert!(c.set_transfer_fee(100, accounts.eve).is_ok());
+            assert!(c.set_burn_bps(200).is_ok());
+            assert!(c.set_creator_fee(300, Some(accounts.frank)).is_ok());
+            assert!(c.set_consolidated_fee_event(true).is_ok());
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.transfer(accounts.bob, 1_000).is_ok());
+            // one event for the whole move, no separate Burned or CreatorFeePaid
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 1);
+            assert_eq!(c.balance_of(accounts.bob), 940);
+            assert_eq!(c.balance_of(accounts.eve), 10);
+            assert_eq!(c.balance_of(accounts.frank), 30);
+            assert_eq!(c.total_supply(), 9_980);
+            assert_eq!(c.balance_of(accounts.alice), 9_000);
+        }
+
+        #[ink::test]
+        fn burn_to_dead_skips_every_fee() {
+            let mut c = Moo::new_with_minter(true);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.mint(1_000).is_ok());
+            assert!(c.set_transfer_fee(100, accounts.eve).is_ok());
+            assert!(c.set_burn_bps(200).is_ok());
+            assert!(c.set_creator_fee(300, Some(accounts.frank)).is_ok());
+            assert!(c.set_burn_mode(true, accounts.django).is_ok());
+            assert!(c.burn(100).is_ok());
+            assert_eq!(c.balance_of(accounts.django), 100);
+            assert_eq!(c.balance_of(accounts.eve), 0);
+            assert_eq!(c.balance_of(accounts.frank), 0);
+            assert_eq!(c.total_supply(), 1_000);
+            assert_eq!(c.balance_of(accounts.alice), 900);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+        use receiver_mock::{ReceiverMock, ReceiverMockRef};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn transfer_and_call_forwards_received_amount_and_data<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let alice_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let eve_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Eve);
+
+            let mut moo_ctor = MooRef::new();
+            let moo_contract = client
+                .instantiate("moo", &ink_e2e::alice(), &mut moo_ctor)
+                .submit()
+                .await
+                .expect("moo instantiate failed");
+            let mut moo_call = moo_contract.call_builder::<Moo>();
+            let mut receiver_ctor = ReceiverMockRef::new(true);
+            let receiver_contract = client
+                .instantiate("receiver_mock", &ink_e2e::alice(), &mut receiver_ctor)
+                .submit()
+                .await
+                .expect("receiver instantiate failed");
+            let mut receiver_call = receiver_contract.call_builder::<ReceiverMock>();
+            let receiver_acc = receiver_contract.account_id;
+
+            // 1% of every move goes to eve, so the receiver gets 9_900 of 10_000
+            client.call(&ink_e2e::alice(), &moo_call.set_minter(alice_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.mint(1_000_000)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.enable_trading()).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.set_transfer_fee(100, eve_acc)).submit().await?;
+
+            let data_vec = ink::prelude::vec![1u8, 2, 3];
+            let send_msg = moo_call.transfer_and_call(receiver_acc, 10_000, data_vec.clone());
+            client.call(&ink_e2e::alice(), &send_msg).submit().await?;
+            let seen_res = client.call(&ink_e2e::alice(), &receiver_call.last_received()).dry_run().await?;
+            assert_eq!(seen_res.return_value(), Some((alice_acc, 9_900, data_vec)));
+            let held_res = client.call(&ink_e2e::alice(), &moo_call.balance_of(receiver_acc)).dry_run().await?;
+            assert_eq!(held_res.return_value(), 9_900);
+
+            // a rejecting receiver reverts the transfer
+            client.call(&ink_e2e::alice(), &receiver_call.set_accept(false)).submit().await?;
+            let reject_msg = moo_call.transfer_and_call(receiver_acc, 10_000, ink::prelude::vec![4u8]);
+            assert!(client.call(&ink_e2e::alice(), &reject_msg).submit().await.is_err());
+            let held_res = client.call(&ink_e2e::alice(), &moo_call.balance_of(receiver_acc)).dry_run().await?;
+            assert_eq!(held_res.return_value(), 9_900);
+            Ok(())
         }
     }
 }
 
 #[cfg(feature = "ink-as-dependency")]
-pub use self::moo::MooRef;
+pub use self::moo::{Moo, MooRef};
@@ -2,7 +2,12 @@
 
 #[ink::contract]
 mod nfmoo {
-    use ink::storage::Mapping;
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::call::{build_call, ExecutionInput, FromAccountId, Selector};
+    use ink::env::hash::Blake2x256;
+    use ink::env::DefaultEnvironment;
+    use ink::storage::{Lazy, Mapping};
+    use moo::MooRef;
 
     // ⬇️ Moved here from model.rs
     #[ink(storage)]
@@ -10,11 +15,14 @@ mod nfmoo {
         // governance / roles
         pub(crate) owner_acc: AccountId,
         pub(crate) paused_flag: bool,
-        pub(crate) is_minter: Mapping<AccountId, bool>,
+        pub(crate) roles: Mapping<(u8, AccountId), bool>,
 
         // supply controls
         pub(crate) max_supply_opt: Option<u128>,
         pub(crate) supply_cnt: u128,
+        pub(crate) reserved_cnt: u128,
+        pub(crate) burned_cnt: u128,
+        pub(crate) max_per_wallet_opt: Option<u32>,
 
         // enumeration
         pub(crate) next_id: u128,
@@ -22,10 +30,175 @@ mod nfmoo {
         pub(crate) owned_count: Mapping<AccountId, u32>,
         pub(crate) tokens_by_owner: Mapping<(AccountId, u32), u128>,
         pub(crate) owned_index: Mapping<u128, u32>,
+        // ids that were minted and later burned; never handed out again
+        pub(crate) burned_ids: Mapping<u128, ()>,
+
+        // id assignment (shuffled pool slot -> id, absent = identity)
+        pub(crate) shuffle_flag: bool,
+        pub(crate) remaining_ids: Mapping<u128, u128>,
 
         // approvals
         pub(crate) token_approval: Mapping<u128, AccountId>,
         pub(crate) operator_approval: Mapping<(AccountId, AccountId), bool>,
+        // per-owner operator list (swap-remove compaction, like the owner token sets)
+        pub(crate) operator_cnt: Mapping<AccountId, u32>,
+        pub(crate) operator_by_index: Mapping<(AccountId, u32), AccountId>,
+        pub(crate) operator_index: Mapping<(AccountId, AccountId), u32>,
+
+        // marketplace (Moo-priced listings: token -> (seller, price))
+        pub(crate) listings: Mapping<u128, (AccountId, Balance)>,
+        pub(crate) moo_acc_opt: Option<AccountId>,
+        pub(crate) treasury_acc: AccountId,
+        pub(crate) market_fee_bps: u16,
+
+        // treasury: native balance kept back from withdrawals
+        pub(crate) min_reserve_balance: Balance,
+
+        // mint recipient policy
+        pub(crate) block_contract_recipients: bool,
+
+        // approval hygiene
+        pub(crate) clear_operators_on_transfer: bool,
+        pub(crate) allow_operator_burn: bool,
+        // grant stamps: bumped on every operator grant, so tokens can tell old grants from new
+        pub(crate) operator_grant_seq: u64,
+        pub(crate) operator_granted_at: Mapping<(AccountId, AccountId), u64>,
+        // (token, previous owner) -> stamp as the token left that owner with
+        // `clear_operators_on_transfer` on; grants stamped at or below it don't cover it
+        pub(crate) token_operator_floor: Mapping<(TokenId, AccountId), u64>,
+
+        // public sale (native price per token; `None` = closed) and revenue split in bps
+        pub(crate) price_opt: Option<Balance>,
+        pub(crate) payout_shares: Vec<(AccountId, u16)>,
+        // rising public price: with the flag on, each token sold adds `price_step`
+        pub(crate) price_step: Balance,
+        pub(crate) price_increase_per_mint_flag: bool,
+        // native fee per token even for privileged `mint_n` (0 = free); kept for `withdraw`
+        pub(crate) minter_fee: Balance,
+
+        // compliance: admin-frozen tokens (no transfer/burn/approve)
+        pub(crate) token_frozen: Mapping<u128, bool>,
+
+        // Moo-priced mint (per token; `None` = closed), paid to `treasury_acc`
+        pub(crate) price_in_moo_opt: Option<Balance>,
+
+        // fractionalization: token -> vault holding it, and who locked it (redeem target)
+        pub(crate) fractionalized: Mapping<u128, AccountId>,
+        pub(crate) fractional_locker: Mapping<u128, AccountId>,
+
+        // transfer hook: `before_transfer(from, to, token_id)` may veto any move.
+        // The lock is `Lazy` so it is written through and visible to re-entrant calls.
+        pub(crate) transfer_hook_opt: Option<AccountId>,
+        pub(crate) hook_lock: Lazy<bool>,
+
+        // metadata: base URI or template with `{id}` placeholders
+        pub(crate) base_uri: String,
+        pub(crate) emit_uri_on_mint: bool,
+
+        // next expected permit nonce per owner (sequential)
+        pub(crate) nft_nonces: Mapping<AccountId, u64>,
+
+        // optional per-token tier label set by the admin
+        pub(crate) token_tier: Mapping<u128, u8>,
+
+        // royalties: collection default and per-token overrides, (recipient, bps)
+        pub(crate) default_royalty_opt: Option<(AccountId, u16)>,
+        pub(crate) token_royalty: Mapping<u128, (AccountId, u16)>,
+
+        // vesting locks: token -> timestamp (ms) before which it can't be transferred
+        pub(crate) unlock_time: Mapping<u128, u64>,
+
+        // self-claimable mints granted by the admin (recipient pays gas)
+        pub(crate) mint_allowance: Mapping<AccountId, u32>,
+
+        // migration lock: while set, only moves touching a whitelisted account succeed
+        pub(crate) transfers_locked_flag: bool,
+        pub(crate) transfer_whitelist: Mapping<AccountId, bool>,
+
+        // claim-based airdrop: per-account token counts, claimed all at once
+        pub(crate) airdrop_alloc: Mapping<AccountId, u32>,
+
+        // anti-spam: when required, recipients must `opt_in` before receiving tokens
+        pub(crate) require_opt_in_flag: bool,
+        pub(crate) opt_in_mints_flag: bool,
+        pub(crate) opted_in: Mapping<AccountId, bool>,
+
+        // lock counters behind `locked_balance_of`: admin-frozen tokens per owner, and
+        // tokens from `mint_locked` per owner within a `(from_ts, until_ts)` vesting window
+        pub(crate) frozen_cnt: Mapping<AccountId, u32>,
+        pub(crate) vesting_cnt: Mapping<AccountId, u32>,
+        pub(crate) vesting_window: Mapping<AccountId, (u64, u64)>,
+
+        // marketplace royalties (in Moo) held by this contract until the recipient pulls them
+        pub(crate) royalty_owed: Mapping<AccountId, Balance>,
+
+        // marketplace pre-approved for every owner unless they explicitly opt out
+        pub(crate) default_operator_opt: Option<AccountId>,
+
+        // holder snapshots: per-account (epoch, owned_count) checkpoints written lazily
+        pub(crate) holders_epoch: u32,
+        pub(crate) hold_ckpt_cnt: Mapping<AccountId, u32>,
+        pub(crate) hold_ckpt: Mapping<(AccountId, u32), (u32, u32)>,
+
+        // burn-to-redeem: Moo paid per tier when a tiered token is redeemed
+        pub(crate) tier_reward: Mapping<u8, Balance>,
+
+        // pull-payment queue for sale proceeds (instead of pushing to payees)
+        pub(crate) queue_proceeds_flag: bool,
+        pub(crate) pending_withdrawals: Mapping<AccountId, Balance>,
+        pub(crate) total_pending: Balance,
+
+        // anti-flip: blocks a token must stay with its holder before it can move (0 = off)
+        pub(crate) min_hold_blocks: u32,
+        pub(crate) acquired_block: Mapping<TokenId, u32>,
+
+        // NFT-to-Moo wrapper: custody token -> account that wrapped it, Moo minted per token
+        pub(crate) wrap_unit: Balance,
+        pub(crate) wrapped: Mapping<TokenId, AccountId>,
+        pub(crate) wrapped_cnt: u32,
+
+        // PSP22 payment tokens accepted by `mint_with_token`: token -> price per NFT
+        pub(crate) accepted_tokens: Mapping<AccountId, Balance>,
+        pub(crate) accepted_token_list: Vec<AccountId>,
+
+        // per-phase allocations, keyed by `SALE_*` bit: cap and tokens minted so far
+        pub(crate) phase_cap: Mapping<u8, u32>,
+        pub(crate) phase_minted: Mapping<u8, u32>,
+
+        // hard cap on tokens any one account can hold (mints and transfers in)
+        pub(crate) max_tokens_per_owner: Option<u32>,
+
+        // reveal gate: once revealed stays revealed; optionally no moves before that
+        pub(crate) revealed_flag: bool,
+        pub(crate) lock_until_reveal_flag: bool,
+        // staged reveal: ids below `revealed_up_to` show real metadata, the rest the placeholder
+        pub(crate) placeholder_uri: String,
+        pub(crate) revealed_up_to: u128,
+
+        // resale throttle: blocks between marketplace sales of the same token
+        pub(crate) resale_cooldown_blocks: u32,
+        pub(crate) resale_cooldown_direct_flag: bool,
+        pub(crate) last_sale_block: Mapping<TokenId, u32>,
+
+        // accept-to-receive escrow: token held by this contract -> (sender, recipient)
+        pub(crate) pending_transfer: Mapping<TokenId, (AccountId, AccountId)>,
+
+        // lifetime mints credited to each account (kept after transfers and burns)
+        pub(crate) minted_by: Mapping<AccountId, u32>,
+
+        // provenance: ownership changes per token since mint (cleared on burn)
+        pub(crate) transfer_count: Mapping<TokenId, u32>,
+
+        // timed operator grants: block timestamp after which the grant lapses (0/absent = never)
+        pub(crate) operator_expiry: Mapping<(AccountId, AccountId), u64>,
+
+        // lifetime revenue totals: Moo royalties from marketplace sales and native mint
+        // payments (`mint_public`/`mint_n`); never reduced by payouts or withdrawals
+        pub(crate) royalties_collected_val: Balance,
+        pub(crate) mint_revenue_val: Balance,
+
+        // withdrawal guardrail: most a single `withdraw`/`withdraw_all` may send (`None` = uncapped)
+        pub(crate) max_single_withdrawal: Option<Balance>,
 
         // versioning
         pub(crate) storage_ver_u32: u32,
@@ -35,6 +208,85 @@ mod nfmoo {
     pub type TokenId = u128;
     pub type Result<T> = core::result::Result<T, Error>;
 
+    // Role ids for `grant_role`/`revoke_role`/`has_role`.
+    pub const ROLE_ADMIN: u8 = 0;
+    pub const ROLE_MINTER: u8 = 1;
+    pub const ROLE_PAUSER: u8 = 2;
+    pub const ROLE_BURNER: u8 = 3;
+
+    /// Most tokens a single mint call may create.
+    pub const MAX_PER_CALL: u32 = 200;
+
+    /// Most payees in a revenue split.
+    pub const MAX_PAYEES: usize = 16;
+    /// Most payment tokens `mint_with_token` accepts at once.
+    pub const MAX_ACCEPTED_TOKENS: usize = 16;
+    /// Largest holding `all_owned_tokens` will return in one call.
+    pub const MAX_ALL_OWNED: u32 = 500;
+    /// Most owners, and most tokens per owner, `tokens_of_many` returns.
+    pub const MAX_MANY_OWNERS: usize = 20;
+    pub const MAX_MANY_PER_OWNER: u32 = 50;
+    /// Most operators `is_approved_for_all_many` checks in one call.
+    pub const MAX_OPERATOR_QUERY: usize = 50;
+    /// URIs in `NFMintedWithUri` are cut to this many bytes.
+    pub const MAX_EVENT_URI_LEN: usize = 256;
+
+    // Storage writes per token behind `estimate_batch_units`, pinned to the real paths by
+    // a test. A mint writes the owner, the three enumeration entries, the acquired block
+    // and the minter's `minted_by` count. A transfer clears the approval, listing and
+    // both fractionalization entries, compacts the sender's list (5), adds the token to
+    // the receiver (3) and writes the owner, acquired block and `transfer_count`.
+    // Each touched account adds a holder checkpoint (2 writes) while snapshots are on, an
+    // operator snapshot adds 1 and a transfer hook's reentrancy lock adds 2. Paid mints
+    // also bump the per-call `phase_minted` counter once per batch.
+    pub const MINT_WRITES_PER_ITEM: u64 = 6;
+    pub const TRANSFER_WRITES_PER_ITEM: u64 = 15;
+    pub const CHECKPOINT_WRITES: u64 = 2;
+    pub const OPERATOR_SNAPSHOT_WRITES: u64 = 1;
+    pub const HOOK_LOCK_WRITES: u64 = 2;
+    pub const BATCH_WRITES: u64 = 1;
+
+    // `CollectionStats::sale_phase` bits: which paid mints are currently open.
+    pub const SALE_PUBLIC: u8 = 1 << 0;
+    pub const SALE_MOO: u8 = 1 << 1;
+
+    // `supports_interface` ids, ERC-165 style: the XOR of the selectors of the messages
+    // that make up each interface, trait-qualified (`PSP34::transfer`, ...) for the PSP34
+    // family as PSP34 tooling derives them. Adding a message to a set changes its id.
+    pub const INTERFACE_DISCOVERY: [u8; 4] = ink::selector_bytes!("supports_interface");
+    pub const INTERFACE_PSP34: [u8; 4] = interface_id(&[
+        ink::selector_bytes!("PSP34::balance_of"),
+        ink::selector_bytes!("PSP34::owner_of"),
+        ink::selector_bytes!("PSP34::get_approved"),
+        ink::selector_bytes!("PSP34::is_approved_for_all"),
+        ink::selector_bytes!("PSP34::approve"),
+        ink::selector_bytes!("PSP34::set_approval_for_all"),
+        ink::selector_bytes!("PSP34::transfer"),
+    ]);
+    pub const INTERFACE_PSP34_METADATA: [u8; 4] = interface_id(&[
+        ink::selector_bytes!("PSP34Metadata::base_uri"),
+        ink::selector_bytes!("PSP34Metadata::token_uri"),
+    ]);
+    pub const INTERFACE_PSP34_ENUMERABLE: [u8; 4] = interface_id(&[
+        ink::selector_bytes!("PSP34Enumerable::tokens_of"),
+        ink::selector_bytes!("PSP34Enumerable::all_owned_tokens"),
+    ]);
+    pub const INTERFACE_ROYALTY: [u8; 4] = ink::selector_bytes!("royalty_info");
+
+    const fn interface_id(selectors: &[[u8; 4]]) -> [u8; 4] {
+        let mut id = [0u8; 4];
+        let mut i = 0;
+        while i < selectors.len() {
+            let mut b = 0;
+            while b < 4 {
+                id[b] ^= selectors[i][b];
+                b += 1;
+            }
+            i += 1;
+        }
+        id
+    }
+
     #[derive(ink::scale::Encode, ink::scale::Decode, Debug, PartialEq, Eq)]
     #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
     pub enum Error {
@@ -46,6 +298,79 @@ mod nfmoo {
         TokenMissing,
         Unauthorized,
         Paused,
+        MintStarted,
+        SupplyUncapped,
+        InvalidRole,
+        StakeRejected,
+        NotListed,
+        MarketUnconfigured,
+        InvalidFee,
+        PaymentFailed,
+        WalletCapReached,
+        ReserveFloor,
+        WithdrawFailed,
+        ContractRecipient,
+        PublicMintClosed,
+        WrongPayment,
+        InvalidShares,
+        PayoutFailed,
+        TokenFrozen,
+        Fractionalized,
+        HookRejected,
+        Reentrant,
+        MaxSupplyReached,
+        InvalidSignature,
+        PermitExpired,
+        InvalidNonce,
+        MooAllowanceLow,
+        MooBalanceLow,
+        TokenLocked,
+        TokenExists,
+        NoMintAllowance,
+        EnumerationIntact,
+        NoAllocation,
+        NotOptedIn,
+        InvalidSnapshot,
+        NoReward,
+        HoldPeriod,
+        WrapOutstanding,
+        TokenNotAccepted,
+        PhaseCapReached,
+        OwnerTokenCap,
+        NotRevealed,
+        ResaleCooldown,
+        RevealBackwards,
+        WithdrawalTooLarge,
+        EnumerationBroken,
+    }
+
+    /// Everything a gallery shows for one token, read in a single call.
+    #[derive(ink::scale::Encode, ink::scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
+    pub struct TokenDetail {
+        pub owner: Option<AccountId>,
+        pub approved: Option<AccountId>,
+        pub uri: Option<String>,
+        /// Frozen by the admin, vesting-locked, or held in a fractionalization vault.
+        pub locked: bool,
+        pub tier: Option<u8>,
+    }
+
+    /// Collection-level dashboard numbers, read in a single call.
+    #[derive(ink::scale::Encode, ink::scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(ink::scale_info::TypeInfo))]
+    pub struct CollectionStats {
+        /// Tokens currently in existence (minted minus burned).
+        pub total_supply: u128,
+        /// `None` for an uncapped collection.
+        pub max_supply: Option<u128>,
+        pub minted_cnt: u128,
+        pub burned_cnt: u128,
+        /// Next sequential id to be drawn (the draw counter when shuffling).
+        pub next_id: u128,
+        /// `SALE_PUBLIC` / `SALE_MOO` bits; 0 when only privileged mints are possible.
+        pub sale_phase: u8,
+        pub paused: bool,
     }
 
     #[ink(event)]
@@ -56,6 +381,16 @@ mod nfmoo {
         pub(crate) token_id: TokenId,
     }
 
+    /// `NFMinted` carrying the token's URI; replaces it while `emit_uri_on_mint` is set.
+    #[ink(event)]
+    pub struct NFMintedWithUri {
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) uri: String,
+    }
+
     #[ink(event)]
     pub struct NFTransferred {
         #[ink(topic)]
@@ -66,6 +401,18 @@ mod nfmoo {
         pub(crate) token_id: TokenId,
     }
 
+    /// Stands in for one `NFTransferred` per id in `first_id..=last_id`; indexers must
+    /// expand it.
+    #[ink(event)]
+    pub struct NFTransferredRange {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) first_id: TokenId,
+        pub(crate) last_id: TokenId,
+    }
+
     #[ink(event)]
     pub struct NFBurned {
         #[ink(topic)]
@@ -93,6 +440,16 @@ mod nfmoo {
         pub(crate) approved_flag: bool,
     }
 
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        pub(crate) owner_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) stake_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+    }
+
     #[ink(event)]
     pub struct PausedSet {
         pub(crate) paused_flag: bool,
@@ -105,318 +462,4940 @@ mod nfmoo {
         pub(crate) enabled_flag: bool,
     }
 
-    // Logic (formerly in logic.rs)
-    use core::cmp::min;
-    use ink::prelude::vec::Vec;
-
-    impl NFMoo {
-        // -------- constructors --------
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        pub(crate) role_u8: u8,
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) by_acc: AccountId,
+    }
 
-        #[ink(constructor)]
-        pub fn new(max_supply_opt: Option<u128>) -> Self {
-            Self {
-                owner_acc: Self::env().caller(),
-                paused_flag: false,
-                is_minter: Default::default(),
-                max_supply_opt,
-                supply_cnt: 0,
-                next_id: 0,
-                owner_by_id: Default::default(),
-                owned_count: Default::default(),
-                tokens_by_owner: Default::default(),
-                owned_index: Default::default(),
-                token_approval: Default::default(),
-                operator_approval: Default::default(),
-                storage_ver_u32: 1,
-            }
-        }
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        pub(crate) role_u8: u8,
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) by_acc: AccountId,
+    }
 
-        // -------- modifiers (helpers) --------
+    #[ink(event)]
+    pub struct PauserSet {
+        #[ink(topic)]
+        pub(crate) pauser_acc: AccountId,
+        pub(crate) enabled_flag: bool,
+    }
 
-        fn only_owner(&self) -> Result<()> {
-            if self.env().caller() != self.owner_acc {
-                return Err(Error::NotOwner)
-            }
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct ShuffleSet {
+        pub(crate) shuffle_flag: bool,
+    }
 
-        fn when_not_paused(&self) -> Result<()> {
-            if self.paused_flag {
-                return Err(Error::Paused)
-            }
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct MarketConfigSet {
+        pub(crate) moo_acc_opt: Option<AccountId>,
+        pub(crate) treasury_acc: AccountId,
+        pub(crate) fee_bps: u16,
+    }
 
-        fn is_approved_or_owner(&self, caller_acc: AccountId, token_id: TokenId) -> Result<()> {
-            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
-            if caller_acc == owner_acc {
-                return Ok(())
-            }
-            if self.token_approval.get(&token_id) == Some(caller_acc) {
-                return Ok(())
-            }
-            if self
-                .operator_approval
-                .get(&(owner_acc, caller_acc))
-                .unwrap_or(false)
-            {
-                return Ok(())
-            }
-            Err(Error::NotApproved)
-        }
+    #[ink(event)]
+    pub struct Listed {
+        #[ink(topic)]
+        pub(crate) seller_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) price_val: Balance,
+    }
 
-        // -------- admin / roles --------
+    #[ink(event)]
+    pub struct Unlisted {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+    }
 
-        #[ink(message)]
-        pub fn set_pause(&mut self, paused_flag: bool) -> Result<()> {
-            self.only_owner()?;
-            self.paused_flag = paused_flag;
-            self.env().emit_event(PausedSet { paused_flag });
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct SoldForMoo {
+        #[ink(topic)]
+        pub(crate) seller_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) buyer_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) price_val: Balance,
+        pub(crate) fee_val: Balance,
+    }
 
-        #[ink(message)]
-        pub fn set_minter(&mut self, minter_acc: AccountId, enabled_flag: bool) -> Result<()> {
-            self.only_owner()?;
-            self.is_minter.insert(&minter_acc, &enabled_flag);
-            self.env().emit_event(MinterSet { minter_acc, enabled_flag });
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct ReserveMinted {
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_cnt: u32,
+        pub(crate) reserved_cnt: u128,
+    }
 
-        // -------- mint / burn / transfer --------
+    #[ink(event)]
+    pub struct MaxPerWalletSet {
+        pub(crate) max_per_wallet_opt: Option<u32>,
+    }
 
-        /// Privileged, bounded mint to caller (minter).
-        #[ink(message)]
-        pub fn mint_n(&mut self, amount_cnt: u32) -> Result<()> {
-            self.when_not_paused()?;
-            if amount_cnt == 0 {
-                return Err(Error::AmountZero)
-            }
-            let caller_acc = self.env().caller();
-            if !self.is_minter.get(&caller_acc).unwrap_or(false) {
-                return Err(Error::Unauthorized)
-            }
+    #[ink(event)]
+    pub struct MinReserveSet {
+        pub(crate) min_reserve_balance: Balance,
+    }
 
-            const MAX_PER_CALL: u32 = 200;
-            if amount_cnt > MAX_PER_CALL {
-                return Err(Error::Overflow)
-            }
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
 
-            for _ in 0..amount_cnt {
-                if let Some(max_supply_val) = self.max_supply_opt {
-                    if self.supply_cnt >= max_supply_val {
-                        return Err(Error::Overflow)
-                    }
-                }
+    #[ink(event)]
+    pub struct ContractRecipientPolicySet {
+        pub(crate) blocked_flag: bool,
+    }
 
-                let token_id = self.next_id;
-                self.next_id = self.next_id.checked_add(1).ok_or(Error::Overflow)?;
+    #[ink(event)]
+    pub struct ClearOperatorsOnTransferSet {
+        pub(crate) enabled_flag: bool,
+    }
 
-                self.owner_by_id.insert(&token_id, &caller_acc);
-                self.add_token_to_owner(caller_acc, token_id)?;
-                self.supply_cnt = self.supply_cnt.checked_add(1).ok_or(Error::Overflow)?;
-                self.env().emit_event(NFMinted { to_acc: caller_acc, token_id });
-            }
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct PriceSet {
+        pub(crate) price_opt: Option<Balance>,
+    }
 
-        /// Transfer a token (caller must be owner or approved).
-        #[ink(message)]
-        pub fn transfer(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
-            self.when_not_paused()?;
-            let caller_acc = self.env().caller();
-            self.is_approved_or_owner(caller_acc, token_id)?;
-            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
-            if from_acc == to_acc {
-                return Err(Error::SameAccount)
-            }
+    #[ink(event)]
+    pub struct PriceStepSet {
+        pub(crate) price_step: Balance,
+        pub(crate) per_mint_flag: bool,
+    }
 
-            self.clear_token_approval(token_id);
-            self.remove_token_from_owner(from_acc, token_id)?;
-            self.owner_by_id.insert(&token_id, &to_acc);
-            self.add_token_to_owner(to_acc, token_id)?;
+    #[ink(event)]
+    pub struct PriceUpdated {
+        pub(crate) price_val: Balance,
+    }
 
-            self.env().emit_event(NFTransferred { from_acc, to_acc, token_id });
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct PayoutSharesSet {
+        pub(crate) payee_cnt: u32,
+    }
 
-        /// Burn a token you own (no operator burn by default).
-        #[ink(message)]
-        pub fn burn(&mut self, token_id: TokenId) -> Result<()> {
-            self.when_not_paused()?;
-            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
-            if from_acc != self.env().caller() {
-                return Err(Error::NotOwner)
-            }
+    #[ink(event)]
+    pub struct RevenueSplit {
+        #[ink(topic)]
+        pub(crate) minter_acc: AccountId,
+        pub(crate) total_val: Balance,
+        pub(crate) payee_cnt: u32,
+    }
 
-            self.clear_token_approval(token_id);
-            self.remove_token_from_owner(from_acc, token_id)?;
-            self.owner_by_id.remove(&token_id);
-            self.supply_cnt = self.supply_cnt.checked_sub(1).ok_or(Error::Overflow)?;
-            self.env().emit_event(NFBurned { from_acc, token_id });
-            Ok(())
-        }
+    #[ink(event)]
+    pub struct TokenFrozenSet {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) frozen_flag: bool,
+    }
 
-        // -------- approvals --------
+    #[ink(event)]
+    pub struct MooPriceSet {
+        pub(crate) price_in_moo_opt: Option<Balance>,
+    }
 
-        #[ink(message)]
-        pub fn approve(&mut self, approved_acc: AccountId, token_id: TokenId) -> Result<()> {
-            self.when_not_paused()?;
-            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
-            if owner_acc != self.env().caller() {
+    #[ink(event)]
+    pub struct FractionalizationLocked {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        #[ink(topic)]
+        pub(crate) vault_acc: AccountId,
+        pub(crate) owner_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FractionalizationRedeemed {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        #[ink(topic)]
+        pub(crate) vault_acc: AccountId,
+        pub(crate) to_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OperatorBurnSet {
+        pub(crate) enabled_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct TransferHookSet {
+        pub(crate) transfer_hook_opt: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct BaseUriSet {
+        pub(crate) base_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct DefaultRoyaltySet {
+        pub(crate) royalty_opt: Option<(AccountId, u16)>,
+    }
+
+    #[ink(event)]
+    pub struct TokenRoyaltySet {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) recipient_acc: AccountId,
+        pub(crate) royalty_bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct LockedMinted {
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        pub(crate) amount_cnt: u32,
+        pub(crate) unlock_ts: u64,
+    }
+
+    #[ink(event)]
+    pub struct EmitUriOnMintSet {
+        pub(crate) enabled_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct MintAllowanceSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) allowance_cnt: u32,
+    }
+
+    #[ink(event)]
+    pub struct EnumerationRepaired {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        #[ink(topic)]
+        pub(crate) owner_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TransfersLockedSet {
+        pub(crate) locked_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct TransferWhitelistSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) allowed_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct AirdropAllocationSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) alloc_cnt: u32,
+    }
+
+    #[ink(event)]
+    pub struct AirdropClaimed {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) amount_cnt: u32,
+    }
+
+    #[ink(event)]
+    pub struct OptInPolicySet {
+        pub(crate) require_flag: bool,
+        pub(crate) mints_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct OptInSet {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        pub(crate) opted_in_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct MinterFeeSet {
+        pub(crate) minter_fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RoyaltyAccrued {
+        #[ink(topic)]
+        pub(crate) recipient_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RoyaltyWithdrawn {
+        #[ink(topic)]
+        pub(crate) recipient_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DefaultOperatorSet {
+        pub(crate) operator_opt: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct HoldersSnapshot {
+        pub(crate) epoch: u32,
+    }
+
+    #[ink(event)]
+    pub struct TierRewardSet {
+        #[ink(topic)]
+        pub(crate) tier: u8,
+        pub(crate) reward_opt: Option<Balance>,
+    }
+
+    #[ink(event)]
+    pub struct Redeemed {
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) tier: u8,
+        pub(crate) reward: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProceedsQueueSet {
+        pub(crate) enabled_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct ProceedsCredited {
+        #[ink(topic)]
+        pub(crate) payee_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProceedsWithdrawn {
+        #[ink(topic)]
+        pub(crate) payee_acc: AccountId,
+        pub(crate) amount_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct MinHoldSet {
+        pub(crate) min_hold_blocks: u32,
+    }
+
+    #[ink(event)]
+    pub struct WrapUnitSet {
+        pub(crate) wrap_unit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Wrapped {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) unit_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unwrapped {
+        #[ink(topic)]
+        pub(crate) acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+        pub(crate) unit_val: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AcceptedTokenSet {
+        #[ink(topic)]
+        pub(crate) token_acc: AccountId,
+        pub(crate) price_opt: Option<Balance>,
+    }
+
+    #[ink(event)]
+    pub struct PhaseCapSet {
+        #[ink(topic)]
+        pub(crate) phase: u8,
+        pub(crate) cap_opt: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct MaxTokensPerOwnerSet {
+        pub(crate) max_tokens_opt: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct RevealLockSet {
+        pub(crate) locked_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct Revealed {
+        #[ink(topic)]
+        pub(crate) by_acc: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ResaleCooldownSet {
+        pub(crate) cooldown_blocks: u32,
+        pub(crate) direct_flag: bool,
+    }
+
+    #[ink(event)]
+    pub struct TransferPending {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+    }
+
+    #[ink(event)]
+    pub struct TransferAccepted {
+        #[ink(topic)]
+        pub(crate) to_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+    }
+
+    #[ink(event)]
+    pub struct TransferCancelled {
+        #[ink(topic)]
+        pub(crate) from_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) token_id: TokenId,
+    }
+
+    #[ink(event)]
+    pub struct PlaceholderUriSet {
+        pub(crate) placeholder_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct RevealedUpTo {
+        pub(crate) token_id: TokenId,
+    }
+
+    #[ink(event)]
+    pub struct OperatorExpirySet {
+        #[ink(topic)]
+        pub(crate) owner_acc: AccountId,
+        #[ink(topic)]
+        pub(crate) operator_acc: AccountId,
+        pub(crate) expiry_ts: u64,
+    }
+
+    #[ink(event)]
+    pub struct MaxWithdrawalSet {
+        pub(crate) max_withdrawal_opt: Option<Balance>,
+    }
+
+    // Logic (formerly in logic.rs)
+    use core::cmp::{max, min};
+    use ink::prelude::string::{String, ToString};
+    use ink::prelude::vec::Vec;
+
+    impl NFMoo {
+        // -------- constructors --------
+
+        #[ink(constructor)]
+        pub fn new(max_supply_opt: Option<u128>) -> Self {
+            Self::new_with_minter(max_supply_opt, false)
+        }
+
+        /// Like `new`, optionally granting the deployer `ROLE_MINTER` (with `MinterSet`)
+        /// so a single-operator deployment can mint right away.
+        #[ink(constructor)]
+        pub fn new_with_minter(max_supply_opt: Option<u128>, auto_minter_flag: bool) -> Self {
+            let owner_acc = Self::env().caller();
+            let mut roles = Mapping::default();
+            roles.insert(&(ROLE_ADMIN, owner_acc), &true);
+            if auto_minter_flag {
+                roles.insert(&(ROLE_MINTER, owner_acc), &true);
+                Self::env().emit_event(MinterSet { minter_acc: owner_acc, enabled_flag: true });
+            }
+            Self {
+                owner_acc,
+                paused_flag: false,
+                roles,
+                max_supply_opt,
+                supply_cnt: 0,
+                burned_cnt: 0,
+                reserved_cnt: 0,
+                max_per_wallet_opt: None,
+                next_id: 0,
+                owner_by_id: Default::default(),
+                owned_count: Default::default(),
+                tokens_by_owner: Default::default(),
+                owned_index: Default::default(),
+                burned_ids: Default::default(),
+                shuffle_flag: false,
+                remaining_ids: Default::default(),
+                token_approval: Default::default(),
+                operator_approval: Default::default(),
+                operator_cnt: Default::default(),
+                operator_by_index: Default::default(),
+                operator_index: Default::default(),
+                listings: Default::default(),
+                moo_acc_opt: None,
+                treasury_acc: owner_acc,
+                market_fee_bps: 0,
+                min_reserve_balance: Self::env().minimum_balance(),
+                block_contract_recipients: false,
+                clear_operators_on_transfer: false,
+                operator_grant_seq: 0,
+                operator_granted_at: Default::default(),
+                token_operator_floor: Default::default(),
+                price_opt: None,
+                payout_shares: Vec::new(),
+                token_frozen: Default::default(),
+                price_in_moo_opt: None,
+                fractionalized: Default::default(),
+                fractional_locker: Default::default(),
+                allow_operator_burn: false,
+                transfer_hook_opt: None,
+                hook_lock: Default::default(),
+                base_uri: String::new(),
+                nft_nonces: Default::default(),
+                token_tier: Default::default(),
+                default_royalty_opt: None,
+                token_royalty: Default::default(),
+                unlock_time: Default::default(),
+                emit_uri_on_mint: false,
+                mint_allowance: Default::default(),
+                price_step: 0,
+                price_increase_per_mint_flag: false,
+                transfers_locked_flag: false,
+                transfer_whitelist: Default::default(),
+                airdrop_alloc: Default::default(),
+                require_opt_in_flag: false,
+                opt_in_mints_flag: false,
+                opted_in: Default::default(),
+                frozen_cnt: Default::default(),
+                vesting_cnt: Default::default(),
+                vesting_window: Default::default(),
+                minter_fee: 0,
+                royalty_owed: Default::default(),
+                default_operator_opt: None,
+                holders_epoch: 0,
+                hold_ckpt_cnt: Default::default(),
+                hold_ckpt: Default::default(),
+                tier_reward: Default::default(),
+                queue_proceeds_flag: false,
+                pending_withdrawals: Default::default(),
+                total_pending: 0,
+                min_hold_blocks: 0,
+                acquired_block: Default::default(),
+                wrap_unit: 0,
+                wrapped: Default::default(),
+                wrapped_cnt: 0,
+                accepted_tokens: Default::default(),
+                accepted_token_list: Vec::new(),
+                phase_cap: Default::default(),
+                phase_minted: Default::default(),
+                max_tokens_per_owner: None,
+                revealed_flag: false,
+                lock_until_reveal_flag: false,
+                resale_cooldown_blocks: 0,
+                resale_cooldown_direct_flag: false,
+                last_sale_block: Default::default(),
+                pending_transfer: Default::default(),
+                placeholder_uri: String::new(),
+                revealed_up_to: 0,
+                minted_by: Default::default(),
+                transfer_count: Default::default(),
+                operator_expiry: Default::default(),
+                royalties_collected_val: 0,
+                mint_revenue_val: 0,
+                max_single_withdrawal: None,
+                storage_ver_u32: 1,
+            }
+        }
+
+        // -------- modifiers (helpers) --------
+
+        /// Admin gate. The deployer (`owner_acc`) bootstraps as the first Admin.
+        fn only_owner(&self) -> Result<()> {
+            if !self.has_role(ROLE_ADMIN, self.env().caller()) {
                 return Err(Error::NotOwner)
             }
-            self.token_approval.insert(&token_id, &approved_acc);
-            self.env().emit_event(NFApproval { owner_acc, approved_acc, token_id });
             Ok(())
         }
 
-        #[ink(message)]
-        pub fn set_approval_for_all(&mut self, operator_acc: AccountId, approved_flag: bool) -> Result<()> {
-            self.when_not_paused()?;
-            let owner_acc = self.env().caller();
-            if owner_acc == operator_acc {
-                return Err(Error::SameAccount)
-            }
-            self.operator_approval.insert(&(owner_acc, operator_acc), &approved_flag);
-            self.env().emit_event(NFApprovalForAll { owner_acc, operator_acc, approved_flag });
-            Ok(())
+        fn only_owner_or_pauser(&self) -> Result<()> {
+            let caller_acc = self.env().caller();
+            if !self.has_role(ROLE_ADMIN, caller_acc) && !self.has_role(ROLE_PAUSER, caller_acc) {
+                return Err(Error::NotOwner)
+            }
+            Ok(())
+        }
+
+        fn when_not_paused(&self) -> Result<()> {
+            if self.paused_flag {
+                return Err(Error::Paused)
+            }
+            Ok(())
+        }
+
+        fn is_approved_or_owner(&self, caller_acc: AccountId, token_id: TokenId) -> Result<()> {
+            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if caller_acc == owner_acc {
+                return Ok(())
+            }
+            if self.token_approval.get(&token_id) == Some(caller_acc) {
+                return Ok(())
+            }
+            if self.is_approved_for_all(owner_acc, caller_acc)
+                && self.operator_covers(owner_acc, caller_acc, token_id)
+            {
+                return Ok(())
+            }
+            Err(Error::NotApproved)
+        }
+
+        /// While a token sits in a fractionalization vault only the vault may move or burn it.
+        fn when_not_fractionalized(&self, caller_acc: AccountId, token_id: TokenId) -> Result<()> {
+            match self.fractionalized.get(&token_id) {
+                Some(vault_acc) if vault_acc != caller_acc => Err(Error::Fractionalized),
+                _ => Ok(()),
+            }
+        }
+
+        fn when_not_frozen(&self, token_id: TokenId) -> Result<()> {
+            if self.token_frozen.get(&token_id).unwrap_or(false) {
+                return Err(Error::TokenFrozen)
+            }
+            Ok(())
+        }
+
+        fn when_opted_in(&self, to_acc: AccountId, mint_flag: bool) -> Result<()> {
+            let applies_flag = self.require_opt_in_flag && (!mint_flag || self.opt_in_mints_flag);
+            if applies_flag && !self.is_opted_in(to_acc) {
+                return Err(Error::NotOptedIn)
+            }
+            Ok(())
+        }
+
+        /// Token-level locks every move checks: reveal lock, freeze, custody (wrapped or
+        /// pending acceptance), hold period, resale cooldown and vesting.
+        fn when_token_movable(&self, token_id: TokenId) -> Result<()> {
+            if self.transfers_reveal_locked() {
+                return Err(Error::NotRevealed)
+            }
+            self.when_not_frozen(token_id)?;
+            if self.wrapped.contains(&token_id) || self.pending_transfer.contains(&token_id) {
+                return Err(Error::TokenLocked)
+            }
+            self.when_hold_elapsed(token_id)?;
+            if self.resale_cooldown_direct_flag {
+                self.when_resale_cooled(token_id)?;
+            }
+            if self.env().block_timestamp() < self.unlock_time_of(token_id) {
+                return Err(Error::TokenLocked)
+            }
+            Ok(())
+        }
+
+        fn when_hold_elapsed(&self, token_id: TokenId) -> Result<()> {
+            let acquired_val = self.acquired_block.get(&token_id).unwrap_or(0);
+            if self.env().block_number().saturating_sub(acquired_val) < self.min_hold_blocks {
+                return Err(Error::HoldPeriod)
+            }
+            Ok(())
+        }
+
+        fn when_resale_cooled(&self, token_id: TokenId) -> Result<()> {
+            if let Some(sale_block) = self.last_sale_block.get(&token_id) {
+                if self.env().block_number().saturating_sub(sale_block) < self.resale_cooldown_blocks {
+                    return Err(Error::ResaleCooldown)
+                }
+            }
+            Ok(())
+        }
+
+        fn when_transfers_open(&self, from_acc: AccountId, to_acc: AccountId) -> Result<()> {
+            if self.transfers_locked_flag
+                && !self.is_transfer_whitelisted(from_acc)
+                && !self.is_transfer_whitelisted(to_acc)
+            {
+                return Err(Error::Paused)
+            }
+            Ok(())
+        }
+
+        // -------- admin / roles --------
+
+        #[ink(message)]
+        pub fn set_pause(&mut self, paused_flag: bool) -> Result<()> {
+            self.only_owner_or_pauser()?;
+            self.paused_flag = paused_flag;
+            self.env().emit_event(PausedSet { paused_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn grant_role(&mut self, role_u8: u8, acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(role_u8, acc, true)
+        }
+
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role_u8: u8, acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(role_u8, acc, false)
+        }
+
+        #[ink(message)]
+        pub fn has_role(&self, role_u8: u8, acc: AccountId) -> bool {
+            self.roles.get(&(role_u8, acc)).unwrap_or(false)
+        }
+
+        /// Shorthand for granting/revoking `ROLE_MINTER`; still emits `MinterSet`.
+        #[ink(message)]
+        pub fn set_minter(&mut self, minter_acc: AccountId, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(ROLE_MINTER, minter_acc, enabled_flag)?;
+            self.env().emit_event(MinterSet { minter_acc, enabled_flag });
+            Ok(())
+        }
+
+        /// Shorthand for `ROLE_PAUSER`: pausers may toggle `set_pause` and nothing else.
+        #[ink(message)]
+        pub fn set_pauser(&mut self, pauser_acc: AccountId, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.set_role(ROLE_PAUSER, pauser_acc, enabled_flag)?;
+            self.env().emit_event(PauserSet { pauser_acc, enabled_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_pauser(&self, acc: AccountId) -> bool {
+            self.has_role(ROLE_PAUSER, acc)
+        }
+
+        /// Switch between sequential and shuffled id assignment. Only possible before
+        /// the first mint, and shuffling needs `max_supply_opt` to bound the id pool.
+        #[ink(message)]
+        pub fn set_shuffle(&mut self, shuffle_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            if self.next_id != 0 {
+                return Err(Error::MintStarted)
+            }
+            if shuffle_flag && self.max_supply_opt.is_none() {
+                return Err(Error::SupplyUncapped)
+            }
+            self.shuffle_flag = shuffle_flag;
+            self.env().emit_event(ShuffleSet { shuffle_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn shuffle_flag(&self) -> bool {
+            self.shuffle_flag
+        }
+
+        // -------- mint / burn / transfer --------
+
+        /// Privileged, bounded mint to caller (minter). With a `minter_fee` set the call
+        /// must carry at least `minter_fee * amount_cnt`; the payment stays in the contract.
+        #[ink(message, payable)]
+        pub fn mint_n(&mut self, amount_cnt: u32) -> Result<()> {
+            let caller_acc = self.env().caller();
+            self.check_mint_n(caller_acc, amount_cnt)?;
+            if self.minter_fee != 0 {
+                let due_val = self
+                    .minter_fee
+                    .checked_mul(Balance::from(amount_cnt))
+                    .ok_or(Error::Overflow)?;
+                if self.env().transferred_value() < due_val {
+                    return Err(Error::WrongPayment)
+                }
+            }
+            self.mint_batch(caller_acc, amount_cnt)?;
+            self.add_mint_revenue(self.env().transferred_value())
+        }
+
+        /// Native fee per token charged on `mint_n`; 0 keeps privileged minting free.
+        #[ink(message)]
+        pub fn set_minter_fee(&mut self, minter_fee: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.minter_fee = minter_fee;
+            self.env().emit_event(MinterFeeSet { minter_fee });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn minter_fee(&self) -> Balance {
+            self.minter_fee
+        }
+
+        /// Dry run of `mint_n(amount_cnt)` called by `acc`: `Ok(())` if it would
+        /// succeed, otherwise the error it would fail with (`Paused`, `Unauthorized`,
+        /// `WalletCapReached`, `MaxSupplyReached`, ...). Shares its checks with `mint_n`;
+        /// the `minter_fee` payment is not part of the dry run.
+        #[ink(message)]
+        pub fn can_mint(&self, acc: AccountId, amount_cnt: u32) -> Result<()> {
+            self.check_mint_n(acc, amount_cnt)
+        }
+
+        /// Rough cost, in storage writes, of a batch mint or transfer of `count` tokens, for
+        /// client-side chunking of oversized batches. An estimate, not gas: it prices every
+        /// item as the heavier transfer path plus whatever the current config adds (holder
+        /// checkpoints, operator snapshots, the hook lock), and one per-batch write.
+        #[ink(message)]
+        pub fn estimate_batch_units(&self, count: u32) -> u64 {
+            if count == 0 {
+                return 0
+            }
+            let mut per_item = max(MINT_WRITES_PER_ITEM, TRANSFER_WRITES_PER_ITEM);
+            if self.holders_epoch != 0 {
+                per_item += 2 * CHECKPOINT_WRITES;
+            }
+            if self.clear_operators_on_transfer {
+                per_item += OPERATOR_SNAPSHOT_WRITES;
+            }
+            if self.transfer_hook_opt.is_some() {
+                per_item += HOOK_LOCK_WRITES;
+            }
+            u64::from(count).saturating_mul(per_item).saturating_add(BATCH_WRITES)
+        }
+
+        /// Minter mint of `amount_cnt` tokens to `to_acc` that can't be transferred until
+        /// `block_timestamp >= unlock_ts` (ms), e.g. staking-reward NFTs.
+        #[ink(message)]
+        pub fn mint_locked(&mut self, to_acc: AccountId, amount_cnt: u32, unlock_ts: u64) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            if !self.has_role(ROLE_MINTER, self.env().caller()) {
+                return Err(Error::Unauthorized)
+            }
+            self.check_wallet_cap(to_acc, amount_cnt)?;
+            // new tokens are appended to the owner's list
+            let first_index = self.balance_of(to_acc);
+            self.mint_batch(to_acc, amount_cnt)?;
+            for index_val in first_index..first_index.saturating_add(amount_cnt) {
+                let token_id = self
+                    .tokens_by_owner
+                    .get(&(to_acc, index_val))
+                    .ok_or(Error::TokenMissing)?;
+                self.unlock_time.insert(&token_id, &unlock_ts);
+            }
+            self.add_vesting(to_acc, amount_cnt, unlock_ts)?;
+            self.env().emit_event(LockedMinted { to_acc, amount_cnt, unlock_ts });
+            Ok(())
+        }
+
+        /// Vesting unlock timestamp (ms) of `token_id`; 0 when it was never locked or has
+        /// changed hands since (only unlocked tokens can move).
+        #[ink(message)]
+        pub fn unlock_time_of(&self, token_id: TokenId) -> u64 {
+            self.unlock_time.get(&token_id).unwrap_or(0)
+        }
+
+        /// Per-token hold period: a token can't move until `min_hold_blocks` blocks after
+        /// it was minted or last received (`HoldPeriod`). Unlike account cooldowns this
+        /// follows the token. Zero disables it.
+        #[ink(message)]
+        pub fn set_min_hold_blocks(&mut self, min_hold_blocks: u32) -> Result<()> {
+            self.only_owner()?;
+            self.min_hold_blocks = min_hold_blocks;
+            self.env().emit_event(MinHoldSet { min_hold_blocks });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_hold_blocks(&self) -> u32 {
+            self.min_hold_blocks
+        }
+
+        /// Block at which `token_id` was minted or last changed hands; `None` if it
+        /// doesn't exist. It can move again at `acquired_block_of + min_hold_blocks`.
+        #[ink(message)]
+        pub fn acquired_block_of(&self, token_id: TokenId) -> Option<u32> {
+            self.acquired_block.get(&token_id)
+        }
+
+        /// Minter mint of a caller-chosen id (e.g. derived from an off-chain asset id).
+        /// Counts against the supply cap and wallet cap like any mint; the sequential and
+        /// shuffled paths skip ids taken this way. Ids are never reused: a live or burned
+        /// id fails with `TokenExists`.
+        #[ink(message)]
+        pub fn mint_with_id(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            if !self.has_role(ROLE_MINTER, self.env().caller()) {
+                return Err(Error::Unauthorized)
+            }
+            if self.id_taken(token_id) {
+                return Err(Error::TokenExists)
+            }
+            if self.block_contract_recipients && self.env().is_contract(&to_acc) {
+                return Err(Error::ContractRecipient)
+            }
+            self.check_wallet_cap(to_acc, 1)?;
+            self.check_supply_room(1)?;
+            self.place_minted(to_acc, token_id)
+        }
+
+        /// Let `acc` mint up to `allowance_cnt` tokens to itself via `claim_mint`. Replaces
+        /// (does not add to) any remaining allowance; 0 revokes it.
+        #[ink(message)]
+        pub fn grant_mint_allowance(&mut self, acc: AccountId, allowance_cnt: u32) -> Result<()> {
+            self.only_owner()?;
+            if allowance_cnt == 0 {
+                self.mint_allowance.remove(&acc);
+            } else {
+                self.mint_allowance.insert(&acc, &allowance_cnt);
+            }
+            self.env().emit_event(MintAllowanceSet { acc, allowance_cnt });
+            Ok(())
+        }
+
+        /// Mint `amount_cnt` tokens to the caller out of its mint allowance.
+        #[ink(message)]
+        pub fn claim_mint(&mut self, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            let caller_acc = self.env().caller();
+            let allowance_cnt = self.mint_allowance_of(caller_acc);
+            if amount_cnt > allowance_cnt {
+                return Err(Error::NoMintAllowance)
+            }
+            self.check_wallet_cap(caller_acc, amount_cnt)?;
+            self.mint_batch(caller_acc, amount_cnt)?;
+            let left_cnt = allowance_cnt - amount_cnt;
+            if left_cnt == 0 {
+                self.mint_allowance.remove(&caller_acc);
+            } else {
+                self.mint_allowance.insert(&caller_acc, &left_cnt);
+            }
+            self.env().emit_event(MintAllowanceSet { acc: caller_acc, allowance_cnt: left_cnt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint_allowance_of(&self, acc: AccountId) -> u32 {
+            self.mint_allowance.get(&acc).unwrap_or(0)
+        }
+
+        /// Allocate `alloc_cnt` airdropped tokens for `acc` to claim (recipient pays the
+        /// mint gas). Replaces any unclaimed allocation; 0 removes it. At most
+        /// `MAX_PER_CALL`, since `claim_airdrop` mints the whole allocation at once.
+        #[ink(message)]
+        pub fn set_airdrop_allocation(&mut self, acc: AccountId, alloc_cnt: u32) -> Result<()> {
+            self.only_owner()?;
+            if alloc_cnt > MAX_PER_CALL {
+                return Err(Error::Overflow)
+            }
+            if alloc_cnt == 0 {
+                self.airdrop_alloc.remove(&acc);
+            } else {
+                self.airdrop_alloc.insert(&acc, &alloc_cnt);
+            }
+            self.env().emit_event(AirdropAllocationSet { acc, alloc_cnt });
+            Ok(())
+        }
+
+        /// Mint the caller's whole airdrop allocation to itself and zero it. Bounded by
+        /// `max_supply_opt` and `max_per_wallet_opt` like every other mint path.
+        #[ink(message)]
+        pub fn claim_airdrop(&mut self) -> Result<()> {
+            self.when_not_paused()?;
+            let caller_acc = self.env().caller();
+            let amount_cnt = self.airdrop_allocation_of(caller_acc);
+            if amount_cnt == 0 {
+                return Err(Error::NoAllocation)
+            }
+            self.check_wallet_cap(caller_acc, amount_cnt)?;
+            self.mint_batch(caller_acc, amount_cnt)?;
+            self.airdrop_alloc.remove(&caller_acc);
+            self.env().emit_event(AirdropClaimed { acc: caller_acc, amount_cnt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn airdrop_allocation_of(&self, acc: AccountId) -> u32 {
+            self.airdrop_alloc.get(&acc).unwrap_or(0)
+        }
+
+        /// Atomic airdrop: any failing recipient reverts the whole batch. The summed
+        /// count across recipients is bounded by `MAX_PER_CALL`.
+        #[ink(message)]
+        pub fn batch_mint(&mut self, recipients_vec: Vec<(AccountId, u32)>) -> Result<()> {
+            self.start_minter_batch(&recipients_vec)?;
+            for (to_acc, amount_cnt) in recipients_vec {
+                if amount_cnt == 0 {
+                    return Err(Error::AmountZero)
+                }
+                self.check_wallet_cap(to_acc, amount_cnt)?;
+                self.mint_batch(to_acc, amount_cnt)?;
+            }
+            Ok(())
+        }
+
+        /// Best-effort airdrop: entries with a zero count or that would exceed the wallet
+        /// cap are skipped and reported as `false`. Hard failures (not a minter, paused,
+        /// supply cap) still abort the whole call.
+        #[ink(message)]
+        pub fn try_batch_mint(&mut self, recipients_vec: Vec<(AccountId, u32)>) -> Result<Vec<bool>> {
+            self.start_minter_batch(&recipients_vec)?;
+            let mut result_vec = Vec::with_capacity(recipients_vec.len());
+            for (to_acc, amount_cnt) in recipients_vec {
+                if amount_cnt == 0 || self.check_wallet_cap(to_acc, amount_cnt).is_err() {
+                    result_vec.push(false);
+                    continue
+                }
+                self.mint_batch(to_acc, amount_cnt)?;
+                result_vec.push(true);
+            }
+            Ok(result_vec)
+        }
+
+        /// Public sale: pay exactly `public_mint_cost(amount_cnt)` in native currency
+        /// (`price * amount_cnt` unless the price rises per mint). With payout shares
+        /// configured the payment is split to payees immediately; otherwise it stays in
+        /// the contract for `withdraw`.
+        #[ink(message, payable)]
+        pub fn mint_public(&mut self, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            let due_val = self.public_mint_cost(amount_cnt)?;
+            let paid_val = self.env().transferred_value();
+            if paid_val != due_val {
+                return Err(Error::WrongPayment)
+            }
+            let caller_acc = self.env().caller();
+            self.check_wallet_cap(caller_acc, amount_cnt)?;
+            self.spend_phase_alloc(SALE_PUBLIC, amount_cnt)?;
+            self.mint_batch(caller_acc, amount_cnt)?;
+            self.raise_price(amount_cnt)?;
+            self.add_mint_revenue(paid_val)?;
+            self.split_revenue(caller_acc, paid_val)
+        }
+
+        /// Lifetime Moo royalties accrued by `buy_with_moo`, withdrawn or not.
+        #[ink(message)]
+        pub fn total_royalties_collected(&self) -> Balance {
+            self.royalties_collected_val
+        }
+
+        /// Lifetime native currency paid into `mint_public` and `mint_n`, withdrawn or not.
+        /// Moo and `mint_with_token` payments go straight to the treasury and aren't counted.
+        #[ink(message)]
+        pub fn total_mint_revenue(&self) -> Balance {
+            self.mint_revenue_val
+        }
+
+        fn add_mint_revenue(&mut self, amount_val: Balance) -> Result<()> {
+            self.mint_revenue_val = self.mint_revenue_val.checked_add(amount_val).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        /// Native cost of the next `amount_cnt` public mints at the live price. With
+        /// per-mint increases on, token `i` of the batch costs `price + i * price_step`.
+        #[ink(message)]
+        pub fn public_mint_cost(&self, amount_cnt: u32) -> Result<Balance> {
+            let price_val = self.price_opt.ok_or(Error::PublicMintClosed)?;
+            let amount_val = Balance::from(amount_cnt);
+            let base_val = price_val.checked_mul(amount_val).ok_or(Error::Overflow)?;
+            if !self.price_increase_per_mint_flag || amount_cnt == 0 {
+                return Ok(base_val)
+            }
+            // price_step * (0 + 1 + ... + (n - 1))
+            let steps_val = amount_val
+                .checked_mul(amount_val - 1)
+                .ok_or(Error::Overflow)?
+                / 2;
+            let rise_val = self.price_step.checked_mul(steps_val).ok_or(Error::Overflow)?;
+            base_val.checked_add(rise_val).ok_or(Error::Overflow)
+        }
+
+        /// Owner-only treasury allocation, independent of the minter role. Still bounded
+        /// by `max_supply_opt`; `reserved_cnt` keeps a running audit total.
+        #[ink(message)]
+        pub fn reserve_mint(&mut self, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            self.only_owner()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            let to_acc = self.env().caller();
+            self.mint_batch(to_acc, amount_cnt)?;
+            self.reserved_cnt = self
+                .reserved_cnt
+                .checked_add(u128::from(amount_cnt))
+                .ok_or(Error::Overflow)?;
+            self.env().emit_event(ReserveMinted { to_acc, amount_cnt, reserved_cnt: self.reserved_cnt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn reserved_count(&self) -> u128 {
+            self.reserved_cnt
+        }
+
+        /// True once a capped collection has no mints left; never true when uncapped.
+        #[ink(message)]
+        pub fn is_sold_out(&self) -> bool {
+            self.remaining_mintable() == Some(0)
+        }
+
+        /// `mintable_remaining` saturated at `u32::MAX`. Reserve mints draw from the same cap.
+        #[ink(message)]
+        pub fn remaining_mintable(&self) -> Option<u32> {
+            self.mintable_remaining()
+                .map(|left_val| u32::try_from(left_val).unwrap_or(u32::MAX))
+        }
+
+        /// Mints left under `max_supply_opt` (`None` when uncapped): `max_supply -
+        /// total_supply`. The cap bounds live supply, so a burn frees a slot, though the
+        /// new token gets a fresh id (ids are never reused). A shuffled collection's id
+        /// pool still runs dry after `max_supply` draws.
+        #[ink(message)]
+        pub fn mintable_remaining(&self) -> Option<u128> {
+            self.max_supply_opt
+                .map(|max_supply_val| max_supply_val.saturating_sub(self.supply_cnt))
+        }
+
+        /// Lifetime counterpart of `mintable_remaining`: `max_supply - minted_cnt`, i.e. as if
+        /// burned tokens still counted. Informational only; minting is bounded by live supply.
+        #[ink(message)]
+        pub fn mintable_remaining_lifetime(&self) -> Option<u128> {
+            self.max_supply_opt
+                .map(|max_supply_val| max_supply_val.saturating_sub(self.minted_total()))
+        }
+
+        /// Open the public sale at `price_opt` per token, or close it with `None`.
+        #[ink(message)]
+        pub fn set_price(&mut self, price_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            self.price_opt = price_opt;
+            self.env().emit_event(PriceSet { price_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn price(&self) -> Option<Balance> {
+            self.price_opt
+        }
+
+        /// Bonding-curve style sale: with `per_mint_flag` on, every public mint raises the
+        /// price by `price_step` per token sold (`PriceUpdated`). Off keeps a flat price.
+        #[ink(message)]
+        pub fn set_price_step(&mut self, price_step: Balance, per_mint_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.price_step = price_step;
+            self.price_increase_per_mint_flag = per_mint_flag;
+            self.env().emit_event(PriceStepSet { price_step, per_mint_flag });
+            Ok(())
+        }
+
+        /// `(price_step, price_increase_per_mint_flag)`.
+        #[ink(message)]
+        pub fn price_step(&self) -> (Balance, bool) {
+            (self.price_step, self.price_increase_per_mint_flag)
+        }
+
+        /// Live public price for the next token (`None` while the sale is closed).
+        #[ink(message)]
+        pub fn current_price(&self) -> Option<Balance> {
+            self.price_opt
+        }
+
+        /// Open the Moo-priced mint at `price_in_moo_opt` per token, or close it with `None`.
+        /// Uses the Moo token from `set_market_config`.
+        #[ink(message)]
+        pub fn set_price_in_moo(&mut self, price_in_moo_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            self.price_in_moo_opt = price_in_moo_opt;
+            self.env().emit_event(MooPriceSet { price_in_moo_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn price_in_moo(&self) -> Option<Balance> {
+            self.price_in_moo_opt
+        }
+
+        /// Total Moo `mint_paying_moo(amount_cnt)` would charge right now, i.e. the allowance
+        /// the buyer must grant this contract. 0 while the Moo mint is closed.
+        #[ink(message)]
+        pub fn quote_mint(&self, amount_cnt: u32) -> Balance {
+            self.moo_mint_cost(amount_cnt).unwrap_or(0)
+        }
+
+        /// Deprecated: use `mint_paying_moo`, which this forwards to unchanged. Kept only so
+        /// integrations built against the original message name keep working.
+        #[ink(message)]
+        pub fn mint_with_moo(&mut self, amount_cnt: u32) -> Result<()> {
+            self.mint_paying_moo(amount_cnt)
+        }
+
+        /// Mint `amount_cnt` tokens paying `quote_mint(amount_cnt)` Moo, pulled from the
+        /// caller's allowance into the treasury, in one transaction. Every precondition is
+        /// checked before the pull so failures name their cause: `MaxSupplyReached`,
+        /// `MooAllowanceLow` (approve this contract first), `MooBalanceLow`; any later
+        /// failure reverts the pull as well.
+        #[ink(message)]
+        pub fn mint_paying_moo(&mut self, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            let due_val = self.moo_mint_cost(amount_cnt)?;
+            let caller_acc = self.env().caller();
+            self.check_wallet_cap(caller_acc, amount_cnt)?;
+            self.check_supply_room(amount_cnt)?;
+            self.spend_phase_alloc(SALE_MOO, amount_cnt)?;
+            self.check_moo_funds(moo_acc, caller_acc, due_val)?;
+            self.pull_moo(moo_acc, caller_acc, self.treasury_acc, due_val)?;
+            self.mint_batch(caller_acc, amount_cnt)
+        }
+
+        /// Accept `token_acc` (a Moo-compatible PSP22) in `mint_with_token` at `price_opt`
+        /// per NFT, or stop accepting it with `None`. At most `MAX_ACCEPTED_TOKENS`.
+        #[ink(message)]
+        pub fn set_accepted_token(&mut self, token_acc: AccountId, price_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            match price_opt {
+                Some(price_val) => {
+                    if !self.accepted_tokens.contains(&token_acc) {
+                        if self.accepted_token_list.len() >= MAX_ACCEPTED_TOKENS {
+                            return Err(Error::Overflow)
+                        }
+                        self.accepted_token_list.push(token_acc);
+                    }
+                    self.accepted_tokens.insert(&token_acc, &price_val);
+                }
+                None => {
+                    self.accepted_tokens.remove(&token_acc);
+                    self.accepted_token_list.retain(|acc| *acc != token_acc);
+                }
+            }
+            self.env().emit_event(AcceptedTokenSet { token_acc, price_opt });
+            Ok(())
+        }
+
+        /// Accepted payment tokens with their price per NFT.
+        #[ink(message)]
+        pub fn accepted_tokens(&self) -> Vec<(AccountId, Balance)> {
+            self.accepted_token_list
+                .iter()
+                .filter_map(|acc| self.accepted_tokens.get(acc).map(|price_val| (*acc, price_val)))
+                .collect()
+        }
+
+        /// `mint_paying_moo` for any accepted token: pulls `price * amount_cnt` of
+        /// `token_acc` from the caller's allowance into the treasury, then mints.
+        /// `TokenNotAccepted` for tokens the owner hasn't configured.
+        #[ink(message)]
+        pub fn mint_with_token(&mut self, token_acc: AccountId, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            let price_val = self.accepted_tokens.get(&token_acc).ok_or(Error::TokenNotAccepted)?;
+            let due_val = price_val.checked_mul(Balance::from(amount_cnt)).ok_or(Error::Overflow)?;
+            let caller_acc = self.env().caller();
+            self.check_wallet_cap(caller_acc, amount_cnt)?;
+            self.check_supply_room(amount_cnt)?;
+            self.check_moo_funds(token_acc, caller_acc, due_val)?;
+            self.pull_moo(token_acc, caller_acc, self.treasury_acc, due_val)?;
+            self.mint_batch(caller_acc, amount_cnt)
+        }
+
+        /// Payees and their bps of each public mint payment; must sum to exactly 10000.
+        /// An empty list disables splitting.
+        #[ink(message)]
+        pub fn set_payout_shares(&mut self, shares_vec: Vec<(AccountId, u16)>) -> Result<()> {
+            self.only_owner()?;
+            if shares_vec.len() > MAX_PAYEES {
+                return Err(Error::InvalidShares)
+            }
+            let total_bps: u32 = shares_vec.iter().map(|(_, bps)| u32::from(*bps)).sum();
+            if !shares_vec.is_empty() && total_bps != 10_000 {
+                return Err(Error::InvalidShares)
+            }
+            let payee_cnt = shares_vec.len() as u32;
+            self.payout_shares = shares_vec;
+            self.env().emit_event(PayoutSharesSet { payee_cnt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn payout_shares(&self) -> Vec<(AccountId, u16)> {
+            self.payout_shares.clone()
+        }
+
+        /// Cap on how many tokens a wallet may hold after a minter mint; `None` disables it.
+        /// Owner reserve mints are exempt.
+        #[ink(message)]
+        pub fn set_max_per_wallet(&mut self, max_per_wallet_opt: Option<u32>) -> Result<()> {
+            self.only_owner()?;
+            self.max_per_wallet_opt = max_per_wallet_opt;
+            self.env().emit_event(MaxPerWalletSet { max_per_wallet_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_per_wallet(&self) -> Option<u32> {
+            self.max_per_wallet_opt
+        }
+
+        /// Hard cap on how many tokens any account may hold, enforced on every mint and
+        /// transfer in (`OwnerTokenCap`) to keep per-owner enumeration bounded. Unlike
+        /// `max_per_wallet` it has no exemptions besides this contract's own custody;
+        /// burns and transfers out are never blocked. `None` disables it.
+        #[ink(message)]
+        pub fn set_max_tokens_per_owner(&mut self, max_tokens_opt: Option<u32>) -> Result<()> {
+            self.only_owner()?;
+            self.max_tokens_per_owner = max_tokens_opt;
+            self.env().emit_event(MaxTokensPerOwnerSet { max_tokens_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_tokens_per_owner(&self) -> Option<u32> {
+            self.max_tokens_per_owner
+        }
+
+        /// When set, every mint path rejects contract accounts as recipients.
+        #[ink(message)]
+        pub fn set_block_contract_recipients(&mut self, blocked_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.block_contract_recipients = blocked_flag;
+            self.env().emit_event(ContractRecipientPolicySet { blocked_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn block_contract_recipients(&self) -> bool {
+            self.block_contract_recipients
+        }
+
+        /// Admin lock on a single token (e.g. reported stolen): blocks transfer, sale,
+        /// burn and approve until unfrozen. Independent of pause and of staking.
+        #[ink(message)]
+        pub fn freeze_token(&mut self, token_id: TokenId, frozen_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if frozen_flag && !self.is_token_frozen(token_id) {
+                let frozen_cnt = self.frozen_cnt.get(&owner_acc).unwrap_or(0);
+                self.token_frozen.insert(&token_id, &true);
+                self.frozen_cnt.insert(&owner_acc, &frozen_cnt.checked_add(1).ok_or(Error::Overflow)?);
+            } else if !frozen_flag && self.is_token_frozen(token_id) {
+                self.token_frozen.remove(&token_id);
+                self.uncount_frozen(owner_acc);
+            }
+            self.env().emit_event(TokenFrozenSet { token_id, frozen_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_token_frozen(&self, token_id: TokenId) -> bool {
+            self.token_frozen.get(&token_id).unwrap_or(false)
+        }
+
+        /// Collection-wide transfer freeze for migrations/snapshots. While locked, every
+        /// token move fails with `Paused` unless the sender or recipient is on the
+        /// transfer whitelist (e.g. the migration contract). Mints are unaffected.
+        #[ink(message)]
+        pub fn set_transfers_locked(&mut self, locked_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.transfers_locked_flag = locked_flag;
+            self.env().emit_event(TransfersLockedSet { locked_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfers_locked(&self) -> bool {
+            self.transfers_locked_flag
+        }
+
+        #[ink(message)]
+        pub fn set_transfer_whitelist(&mut self, acc: AccountId, allowed_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            if allowed_flag {
+                self.transfer_whitelist.insert(&acc, &true);
+            } else {
+                self.transfer_whitelist.remove(&acc);
+            }
+            self.env().emit_event(TransferWhitelistSet { acc, allowed_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_transfer_whitelisted(&self, acc: AccountId) -> bool {
+            self.transfer_whitelist.get(&acc).unwrap_or(false)
+        }
+
+        /// With `require_flag` set, tokens can only be moved to accounts that called
+        /// `opt_in` (else `NotOptedIn`); contract recipients such as vaults must opt in
+        /// too. Mints are exempt unless `mints_flag` is also set.
+        #[ink(message)]
+        pub fn set_require_opt_in(&mut self, require_flag: bool, mints_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.require_opt_in_flag = require_flag;
+            self.opt_in_mints_flag = mints_flag;
+            self.env().emit_event(OptInPolicySet { require_flag, mints_flag });
+            Ok(())
+        }
+
+        /// `(require_opt_in_flag, opt_in_mints_flag)`.
+        #[ink(message)]
+        pub fn require_opt_in(&self) -> (bool, bool) {
+            (self.require_opt_in_flag, self.opt_in_mints_flag)
+        }
+
+        /// Accept incoming tokens while opt-in is required.
+        #[ink(message)]
+        pub fn opt_in(&mut self) -> Result<()> {
+            let acc = self.env().caller();
+            self.opted_in.insert(&acc, &true);
+            self.env().emit_event(OptInSet { acc, opted_in_flag: true });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn opt_out(&mut self) -> Result<()> {
+            let acc = self.env().caller();
+            self.opted_in.remove(&acc);
+            self.env().emit_event(OptInSet { acc, opted_in_flag: false });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_opted_in(&self, acc: AccountId) -> bool {
+            self.opted_in.get(&acc).unwrap_or(false)
+        }
+
+        /// Recovery tool: re-append `token_id` to `owner_acc`'s enumeration when
+        /// `owner_by_id` says they own it but `tokens_of` has lost it. Mints nothing.
+        /// `owner_acc` must match `owner_by_id` (else `TokenMissing`); an entry that is
+        /// already consistent fails with `EnumerationIntact`, and a repair that still
+        /// leaves it inconsistent fails with `EnumerationBroken`.
+        #[ink(message)]
+        pub fn repair_enumeration(&mut self, token_id: TokenId, owner_acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            if self.owner_by_id.get(&token_id) != Some(owner_acc) {
+                return Err(Error::TokenMissing)
+            }
+            if self.enumeration_intact(owner_acc, token_id) {
+                return Err(Error::EnumerationIntact)
+            }
+            self.add_token_to_owner(owner_acc, token_id)?;
+            if !self.enumeration_intact(owner_acc, token_id) {
+                return Err(Error::EnumerationBroken)
+            }
+            self.env().emit_event(EnumerationRepaired { token_id, owner_acc });
+            Ok(())
+        }
+
+        /// Tag a token with a tier (`None` clears it).
+        #[ink(message)]
+        pub fn set_token_tier(&mut self, token_id: TokenId, tier_opt: Option<u8>) -> Result<()> {
+            self.only_owner()?;
+            if self.owner_by_id.get(&token_id).is_none() {
+                return Err(Error::TokenMissing)
+            }
+            if let Some(tier_u8) = tier_opt {
+                self.token_tier.insert(&token_id, &tier_u8);
+            } else {
+                self.token_tier.remove(&token_id);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn tier_of(&self, token_id: TokenId) -> Option<u8> {
+            self.token_tier.get(&token_id)
+        }
+
+        /// Moo paid by `redeem` for tokens of `tier` (`None` disables redeeming that tier).
+        #[ink(message)]
+        pub fn set_tier_reward(&mut self, tier: u8, reward_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            if let Some(reward_val) = reward_opt {
+                self.tier_reward.insert(&tier, &reward_val);
+            } else {
+                self.tier_reward.remove(&tier);
+            }
+            self.env().emit_event(TierRewardSet { tier, reward_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn tier_reward_of(&self, tier: u8) -> Option<Balance> {
+            self.tier_reward.get(&tier)
+        }
+
+        /// Burn the caller's `token_id` and mint its tier's reward in the market's Moo token
+        /// to the caller. This contract must hold `ROLE_MINTER` on Moo. Untiered tokens and
+        /// tiers without a configured reward fail with `NoReward`.
+        #[ink(message)]
+        pub fn redeem(&mut self, token_id: TokenId) -> Result<()> {
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            let caller_acc = self.env().caller();
+            if self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)? != caller_acc {
+                return Err(Error::NotOwner)
+            }
+            let tier = self.tier_of(token_id).ok_or(Error::NoReward)?;
+            let reward = self.tier_reward_of(tier).ok_or(Error::NoReward)?;
+            self.burn(token_id)?;
+            if reward != 0 {
+                let mut moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+                let call_res = moo_ref.call_mut().mint_to(caller_acc, reward).try_invoke();
+                if !matches!(call_res, Ok(Ok(Ok(())))) {
+                    return Err(Error::PayoutFailed)
+                }
+            }
+            self.env().emit_event(Redeemed { token_id, tier, reward });
+            Ok(())
+        }
+
+        /// Moo minted per wrapped token; zero disables `wrap`. Fixed while any token is
+        /// wrapped (`WrapOutstanding`) so every unwrap repays what its wrap minted.
+        #[ink(message)]
+        pub fn set_wrap_unit(&mut self, wrap_unit: Balance) -> Result<()> {
+            self.only_owner()?;
+            if self.wrapped_cnt != 0 {
+                return Err(Error::WrapOutstanding)
+            }
+            self.wrap_unit = wrap_unit;
+            self.env().emit_event(WrapUnitSet { wrap_unit });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn wrap_unit(&self) -> Balance {
+            self.wrap_unit
+        }
+
+        /// Account that wrapped `token_id`, while the token sits in this contract's custody.
+        #[ink(message)]
+        pub fn wrapped_by(&self, token_id: TokenId) -> Option<AccountId> {
+            self.wrapped.get(&token_id)
+        }
+
+        /// Move the caller's `token_id` into this contract's custody and mint `wrap_unit`
+        /// Moo (the market's token) to the caller. This contract must hold `ROLE_MINTER`
+        /// on Moo. Wrapped tokens can only leave custody through `unwrap` and can't be
+        /// burned, not even by `ROLE_BURNER`.
+        #[ink(message)]
+        pub fn wrap(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            if self.wrap_unit == 0 {
+                return Err(Error::MarketUnconfigured)
+            }
+            let caller_acc = self.env().caller();
+            if self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)? != caller_acc {
+                return Err(Error::NotOwner)
+            }
+            self.enter_custody(caller_acc, token_id)?;
+            self.wrapped.insert(&token_id, &caller_acc);
+            self.wrapped_cnt = self.wrapped_cnt.checked_add(1).ok_or(Error::Overflow)?;
+            let unit_val = self.wrap_unit;
+            let mut moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            let call_res = moo_ref.call_mut().mint_to(caller_acc, unit_val).try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::PayoutFailed)
+            }
+            self.env().emit_event(Wrapped { acc: caller_acc, token_id, unit_val });
+            Ok(())
+        }
+
+        /// Burn `wrap_unit` Moo from the caller and return the exact token they wrapped.
+        /// Only the wrapping account may unwrap (`NotOwner`). The burn spends the caller's
+        /// Moo allowance to this contract, which must hold `ROLE_BURNER` on Moo;
+        /// `MooAllowanceLow` / `MooBalanceLow` when the caller can't repay.
+        #[ink(message)]
+        pub fn unwrap(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            let caller_acc = self.env().caller();
+            if self.wrapped.get(&token_id) != Some(caller_acc) {
+                return Err(Error::NotOwner)
+            }
+            let unit_val = self.wrap_unit;
+            self.check_moo_funds(moo_acc, caller_acc, unit_val)?;
+            let mut moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            let call_res = moo_ref.call_mut().burn_from(caller_acc, unit_val).try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::PaymentFailed)
+            }
+            self.wrapped.remove(&token_id);
+            self.wrapped_cnt = self.wrapped_cnt.checked_sub(1).ok_or(Error::Overflow)?;
+            self.leave_custody(caller_acc, caller_acc, token_id)?;
+            self.env().emit_event(Unwrapped { acc: caller_acc, token_id, unit_val });
+            Ok(())
+        }
+
+        /// Transfer a token (caller must be owner or approved).
+        #[ink(message)]
+        pub fn transfer(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let caller_acc = self.env().caller();
+            self.is_approved_or_owner(caller_acc, token_id)?;
+            self.when_not_fractionalized(caller_acc, token_id)?;
+            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if from_acc == to_acc {
+                return Err(Error::SameAccount)
+            }
+            self.move_token(from_acc, to_acc, token_id)
+        }
+
+        /// Dry run of `transfer` of `token_id` by `caller_acc`: `Ok(())` if it would pass,
+        /// otherwise the error `transfer` would fail with (`Paused`, `NotApproved`,
+        /// `TokenFrozen`, `TokenLocked`, `HoldPeriod`, `NotRevealed`, `NotOptedIn`, ...).
+        /// Shares its checks with the real path. With `to_opt = None` only the sender side
+        /// is checked, and during a migration lock a non-whitelisted sender reports
+        /// `Paused`; pass the recipient to add `SameAccount`, opt-in and its whitelist
+        /// entry. The transfer hook is a live call and never runs here.
+        #[ink(message)]
+        pub fn can_transfer(&self, caller_acc: AccountId, token_id: TokenId, to_opt: Option<AccountId>) -> Result<()> {
+            self.when_not_paused()?;
+            self.is_approved_or_owner(caller_acc, token_id)?;
+            self.when_not_fractionalized(caller_acc, token_id)?;
+            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            match to_opt {
+                Some(to_acc) => {
+                    if from_acc == to_acc {
+                        return Err(Error::SameAccount)
+                    }
+                    self.when_transfers_open(from_acc, to_acc)?;
+                    self.when_opted_in(to_acc, false)?;
+                }
+                None => self.when_transfers_open(from_acc, from_acc)?,
+            }
+            self.when_token_movable(token_id)
+        }
+
+        /// `transfer` that only goes through while `expected_owner` still owns `token_id`
+        /// (`NotOwner` otherwise), so an escrow settling a sale can't race a seller who
+        /// moved the token. Approval checks are the usual ones.
+        #[ink(message)]
+        pub fn transfer_if_owner(&mut self, expected_owner: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            if self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)? != expected_owner {
+                return Err(Error::NotOwner)
+            }
+            self.transfer(to_acc, token_id)
+        }
+
+        /// Two-step transfer for high-value tokens: moves `token_id` into this contract's
+        /// custody until `to_acc` calls `accept_transfer`, or the sender takes it back with
+        /// `cancel_transfer`. Usual approval checks; the token can't move any other way
+        /// while pending.
+        #[ink(message)]
+        pub fn transfer_pending(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let caller_acc = self.env().caller();
+            self.is_approved_or_owner(caller_acc, token_id)?;
+            self.when_not_fractionalized(caller_acc, token_id)?;
+            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if from_acc == to_acc {
+                return Err(Error::SameAccount)
+            }
+            self.enter_custody(from_acc, token_id)?;
+            self.pending_transfer.insert(&token_id, &(from_acc, to_acc));
+            self.env().emit_event(TransferPending { from_acc, to_acc, token_id });
+            Ok(())
+        }
+
+        /// Recipient-only: claim a pending transfer addressed to the caller.
+        #[ink(message)]
+        pub fn accept_transfer(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let (from_acc, to_acc) = self.pending_transfer.get(&token_id).ok_or(Error::TokenMissing)?;
+            if to_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            self.leave_custody(from_acc, to_acc, token_id)?;
+            self.pending_transfer.remove(&token_id);
+            self.env().emit_event(TransferAccepted { to_acc, token_id });
+            Ok(())
+        }
+
+        /// Sender-only: take back a transfer that hasn't been accepted yet.
+        #[ink(message)]
+        pub fn cancel_transfer(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let (from_acc, _) = self.pending_transfer.get(&token_id).ok_or(Error::TokenMissing)?;
+            if from_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            self.leave_custody(from_acc, from_acc, token_id)?;
+            self.pending_transfer.remove(&token_id);
+            self.env().emit_event(TransferCancelled { from_acc, token_id });
+            Ok(())
+        }
+
+        /// Times `token_id` changed hands since it was minted: transfers, batch moves,
+        /// marketplace sales, vault moves and accepted pending transfers count once each.
+        /// This contract's own custody legs (wrapping, escrow) don't. Survives ownership
+        /// changes; 0 after a burn.
+        #[ink(message)]
+        pub fn transfer_count_of(&self, token_id: TokenId) -> u32 {
+            self.transfer_count.get(&token_id).unwrap_or(0)
+        }
+
+        /// Tokens ever minted to `acc` by any mint path, whatever it holds now (e.g. for
+        /// "OG minter" rewards).
+        #[ink(message)]
+        pub fn minted_count_of(&self, acc: AccountId) -> u32 {
+            self.minted_by.get(&acc).unwrap_or(0)
+        }
+
+        /// `(sender, recipient)` of a pending transfer.
+        #[ink(message)]
+        pub fn pending_transfer_of(&self, token_id: TokenId) -> Option<(AccountId, AccountId)> {
+            self.pending_transfer.get(&token_id)
+        }
+
+        /// Install (or clear) a contract whose `before_transfer(from, to, token_id)` is called
+        /// before every ownership move and must return `Ok(())` (as `Result<(), u8>`).
+        #[ink(message)]
+        pub fn set_transfer_hook(&mut self, transfer_hook_opt: Option<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            self.transfer_hook_opt = transfer_hook_opt;
+            self.env().emit_event(TransferHookSet { transfer_hook_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_hook(&self) -> Option<AccountId> {
+            self.transfer_hook_opt
+        }
+
+        /// Send each token to its paired recipient (at most `MAX_PER_CALL` legs), with the
+        /// same approval checks as `transfer` per token and one `NFTransferred` per leg.
+        /// Any failing leg reverts the whole call.
+        #[ink(message)]
+        pub fn batch_transfer_mixed(&mut self, transfers_vec: Vec<(AccountId, TokenId)>) -> Result<()> {
+            if transfers_vec.len() > MAX_PER_CALL as usize {
+                return Err(Error::Overflow)
+            }
+            for (to_acc, token_id) in transfers_vec {
+                self.transfer(to_acc, token_id)?;
+            }
+            Ok(())
+        }
+
+        /// Move every id in `first_id..=last_id` (at most `MAX_PER_CALL`) to `to_acc`; the
+        /// caller must own all of them. Emits a single `NFTransferredRange` instead of one
+        /// `NFTransferred` per token, so indexers must handle the range event.
+        #[ink(message)]
+        pub fn transfer_range(&mut self, to_acc: AccountId, first_id: TokenId, last_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let from_acc = self.env().caller();
+            if from_acc == to_acc {
+                return Err(Error::SameAccount)
+            }
+            let span_val = last_id.checked_sub(first_id).ok_or(Error::AmountZero)?;
+            if span_val >= u128::from(MAX_PER_CALL) {
+                return Err(Error::Overflow)
+            }
+            for token_id in first_id..=last_id {
+                if self.owner_by_id.get(&token_id) != Some(from_acc) {
+                    return Err(Error::NotOwner)
+                }
+            }
+            for token_id in first_id..=last_id {
+                self.relocate_token(from_acc, to_acc, token_id)?;
+            }
+            self.env().emit_event(NFTransferredRange { from_acc, to_acc, first_id, last_id });
+            Ok(())
+        }
+
+        /// Transfer `token_id` to `stake_acc`, then call its `on_stake(owner, token_id, data)`
+        /// (expected to return `Result<(), u8>`). Any callback failure reverts the transfer.
+        #[ink(message)]
+        pub fn transfer_and_stake(&mut self, stake_acc: AccountId, token_id: TokenId, data_vec: Vec<u8>) -> Result<()> {
+            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            self.transfer(stake_acc, token_id)?;
+
+            let call_res = build_call::<DefaultEnvironment>()
+                .call(stake_acc)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("on_stake")))
+                        .push_arg(owner_acc)
+                        .push_arg(token_id)
+                        .push_arg(data_vec),
+                )
+                .returns::<core::result::Result<(), u8>>()
+                .try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::StakeRejected)
+            }
+            self.env().emit_event(Staked { owner_acc, stake_acc, token_id });
+            Ok(())
+        }
+
+        /// Burn a token you own. Holders of `ROLE_BURNER` (e.g. a game contract) may burn
+        /// any token. While `allow_operator_burn` is on, burn authority matches transfer
+        /// authority: an operator approved for all may burn any of the owner's tokens, a
+        /// single-token `approve` covers only that token.
+        /// `NFBurned` always names the token's owner, whoever triggered the burn.
+        #[ink(message)]
+        pub fn burn(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let from_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            let caller_acc = self.env().caller();
+            if from_acc != caller_acc && !self.has_role(ROLE_BURNER, caller_acc) {
+                if !self.allow_operator_burn {
+                    return Err(Error::NotOwner)
+                }
+                self.is_approved_or_owner(caller_acc, token_id)?;
+            }
+            self.when_not_frozen(token_id)?;
+            self.when_not_fractionalized(caller_acc, token_id)?;
+            // escrowed for its recipient or backing wrapped Moo: cancel, accept or unwrap first
+            if self.pending_transfer.contains(&token_id) || self.wrapped.contains(&token_id) {
+                return Err(Error::TokenLocked)
+            }
+
+            self.clear_token_approval(token_id);
+            self.clear_fractionalization(token_id);
+            self.token_royalty.remove(&token_id);
+            self.token_tier.remove(&token_id);
+            self.acquired_block.remove(&token_id);
+            self.last_sale_block.remove(&token_id);
+            self.transfer_count.remove(&token_id);
+            self.token_operator_floor.remove(&(token_id, from_acc));
+            self.release_vesting(from_acc, token_id);
+            if self.token_frozen.contains(&token_id) {
+                self.token_frozen.remove(&token_id);
+                self.uncount_frozen(from_acc);
+            }
+            self.remove_token_from_owner(from_acc, token_id)?;
+            self.owner_by_id.remove(&token_id);
+            self.burned_ids.insert(&token_id, &());
+            self.supply_cnt = self.supply_cnt.checked_sub(1).ok_or(Error::Overflow)?;
+            self.burned_cnt = self.burned_cnt.checked_add(1).ok_or(Error::Overflow)?;
+            self.env().emit_event(NFBurned { from_acc, token_id });
+            Ok(())
+        }
+
+        // -------- fractionalization --------
+
+        /// Move a token you own into `vault_acc` and record the vault as its sole mover
+        /// until `redeem_from_fractionalization`.
+        #[ink(message)]
+        pub fn lock_for_fractionalization(&mut self, token_id: TokenId, vault_acc: AccountId) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if owner_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            if owner_acc == vault_acc {
+                return Err(Error::SameAccount)
+            }
+            self.move_token(owner_acc, vault_acc, token_id)?;
+            self.fractionalized.insert(&token_id, &vault_acc);
+            self.fractional_locker.insert(&token_id, &owner_acc);
+            self.env().emit_event(FractionalizationLocked { token_id, vault_acc, owner_acc });
+            Ok(())
+        }
+
+        /// Vault-only: release the token back to the account that locked it.
+        #[ink(message)]
+        pub fn redeem_from_fractionalization(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let vault_acc = self.fractionalized.get(&token_id).ok_or(Error::TokenMissing)?;
+            if vault_acc != self.env().caller() {
+                return Err(Error::Unauthorized)
+            }
+            let to_acc = self.fractional_locker.get(&token_id).ok_or(Error::TokenMissing)?;
+            self.move_token(vault_acc, to_acc, token_id)?;
+            self.env().emit_event(FractionalizationRedeemed { token_id, vault_acc, to_acc });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fractionalized_vault(&self, token_id: TokenId) -> Option<AccountId> {
+            self.fractionalized.get(&token_id)
+        }
+
+        // -------- approvals --------
+
+        #[ink(message)]
+        pub fn approve(&mut self, approved_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if owner_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            self.when_not_frozen(token_id)?;
+            self.token_approval.insert(&token_id, &approved_acc);
+            self.env().emit_event(NFApproval { owner_acc, approved_acc, token_id });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_approval_for_all(&mut self, operator_acc: AccountId, approved_flag: bool) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            if owner_acc == operator_acc {
+                return Err(Error::SameAccount)
+            }
+            self.set_operator(owner_acc, operator_acc, approved_flag)
+        }
+
+        /// `set_approval_for_all(operator_acc, true)` that stops counting once the block
+        /// timestamp passes `expiry_ts`; 0 grants without expiry. The operator stays listed
+        /// by `operators_of` after it lapses until the owner revokes it.
+        #[ink(message)]
+        pub fn set_approval_for_all_until(&mut self, operator_acc: AccountId, expiry_ts: u64) -> Result<()> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            if owner_acc == operator_acc {
+                return Err(Error::SameAccount)
+            }
+            if expiry_ts != 0 && expiry_ts <= self.env().block_timestamp() {
+                return Err(Error::PermitExpired)
+            }
+            self.set_operator(owner_acc, operator_acc, true)?;
+            if expiry_ts != 0 {
+                self.operator_expiry.insert(&(owner_acc, operator_acc), &expiry_ts);
+            }
+            self.env().emit_event(OperatorExpirySet { owner_acc, operator_acc, expiry_ts });
+            Ok(())
+        }
+
+        /// 0 when the grant has no expiry (or there is no grant).
+        #[ink(message)]
+        pub fn operator_expiry_of(&self, owner_acc: AccountId, operator_acc: AccountId) -> u64 {
+            self.operator_expiry.get(&(owner_acc, operator_acc)).unwrap_or(0)
+        }
+
+        /// Gasless `set_approval_for_all`: `signature_val` is `owner_acc`'s ECDSA signature over
+        /// `(contract, owner, operator, approved, deadline, nonce)`. `deadline_ms` is compared to
+        /// the block timestamp and `nonce_val` must equal `nft_nonce(owner_acc)`.
+        #[ink(message)]
+        pub fn permit_approval_for_all(
+            &mut self,
+            owner_acc: AccountId,
+            operator_acc: AccountId,
+            approved_flag: bool,
+            deadline_ms: u64,
+            nonce_val: u64,
+            signature_val: [u8; 65],
+        ) -> Result<()> {
+            self.when_not_paused()?;
+            if owner_acc == operator_acc {
+                return Err(Error::SameAccount)
+            }
+            if self.env().block_timestamp() > deadline_ms {
+                return Err(Error::PermitExpired)
+            }
+            let expected_nonce = self.nft_nonce(owner_acc);
+            if nonce_val != expected_nonce {
+                return Err(Error::InvalidNonce)
+            }
+            let msg_hash = self.env().hash_encoded::<Blake2x256, _>(&(
+                self.env().account_id(),
+                owner_acc,
+                operator_acc,
+                approved_flag,
+                deadline_ms,
+                nonce_val,
+            ));
+            if self.recover_signer(&msg_hash, &signature_val)? != owner_acc {
+                return Err(Error::InvalidSignature)
+            }
+            self.nft_nonces.insert(&owner_acc, &expected_nonce.checked_add(1).ok_or(Error::Overflow)?);
+            self.set_operator(owner_acc, operator_acc, approved_flag)
+        }
+
+        #[ink(message)]
+        pub fn nft_nonce(&self, owner_acc: AccountId) -> u64 {
+            self.nft_nonces.get(&owner_acc).unwrap_or(0)
+        }
+
+        /// Revoke up to `limit_cnt` of the caller's operators, most recent first.
+        /// Returns how many were revoked; size `limit_cnt` from `operator_count`.
+        #[ink(message)]
+        pub fn revoke_all_operators(&mut self, limit_cnt: u32) -> Result<u32> {
+            self.when_not_paused()?;
+            let owner_acc = self.env().caller();
+            let mut revoked_cnt: u32 = 0;
+            while revoked_cnt < limit_cnt {
+                let count_val = self.operator_count(owner_acc);
+                if count_val == 0 {
+                    break
+                }
+                let operator_acc = self
+                    .operator_by_index
+                    .get(&(owner_acc, count_val - 1))
+                    .ok_or(Error::TokenMissing)?;
+                self.set_operator(owner_acc, operator_acc, false)?;
+                revoked_cnt += 1;
+            }
+            if let Some(default_acc) = self.default_operator_opt {
+                if revoked_cnt < limit_cnt && self.is_approved_for_all(owner_acc, default_acc) {
+                    self.set_operator(owner_acc, default_acc, false)?;
+                    revoked_cnt += 1;
+                }
+            }
+            Ok(revoked_cnt)
+        }
+
+        /// When enabled, a token leaving an account drops that account's existing operator
+        /// grants for that token only: if it ever comes back, operators approved before it
+        /// left no longer cover it, while the seller's other tokens keep their approvals.
+        /// The receiver's own operators are unaffected. Implemented as a per-(token, previous
+        /// owner) snapshot of the grant counter, so each transfer does one extra write
+        /// regardless of `operator_count`. Storage cost: one `u64` per distinct (token,
+        /// previous owner) pair moved while enabled, never reclaimed except the burning
+        /// owner's, plus one `u64` per operator grant (kept whether or not it's enabled).
+        /// The default operator is not affected.
+        #[ink(message)]
+        pub fn set_clear_operators_on_transfer(&mut self, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.clear_operators_on_transfer = enabled_flag;
+            self.env().emit_event(ClearOperatorsOnTransferSet { enabled_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn clear_operators_on_transfer(&self) -> bool {
+            self.clear_operators_on_transfer
+        }
+
+        /// Let approved accounts and operators burn tokens they could transfer.
+        #[ink(message)]
+        pub fn set_allow_operator_burn(&mut self, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.allow_operator_burn = enabled_flag;
+            self.env().emit_event(OperatorBurnSet { enabled_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn allow_operator_burn(&self) -> bool {
+            self.allow_operator_burn
+        }
+
+        /// Number of operators currently approved for all of `owner_acc`'s tokens.
+        #[ink(message)]
+        pub fn operator_count(&self, owner_acc: AccountId) -> u32 {
+            self.operator_cnt.get(&owner_acc).unwrap_or(0)
+        }
+
+        /// Paginated list of operators approved by `owner_acc` (order not stable across revokes).
+        #[ink(message)]
+        pub fn operators_of(&self, owner_acc: AccountId, start_index: u32, limit_cnt: u32) -> Vec<AccountId> {
+            let count_val = self.operator_count(owner_acc);
+            if start_index >= count_val || limit_cnt == 0 {
+                return Vec::new()
+            }
+            let end_index = min(count_val, start_index.saturating_add(limit_cnt));
+            let mut list_vec: Vec<AccountId> = Vec::new();
+            let mut index_val = start_index;
+            while index_val < end_index {
+                if let Some(operator_acc) = self.operator_by_index.get(&(owner_acc, index_val)) {
+                    list_vec.push(operator_acc);
+                }
+                index_val += 1;
+            }
+            list_vec
+        }
+
+        /// `operators_of` paired with each operator's live `is_approved_for_all` flag, for
+        /// auditing. Revokes swap-remove from the list, so every entry is currently `true`.
+        #[ink(message)]
+        pub fn approved_operators(&self, owner_acc: AccountId, start_index: u32, limit_cnt: u32) -> Vec<(AccountId, bool)> {
+            self.operators_of(owner_acc, start_index, limit_cnt)
+                .into_iter()
+                .map(|operator_acc| (operator_acc, self.is_approved_for_all(owner_acc, operator_acc)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_approved(&self, token_id: TokenId) -> Option<AccountId> {
+            self.token_approval.get(&token_id)
+        }
+
+        /// An owner's explicit `set_approval_for_all` choice wins; with none recorded, the
+        /// collection's default operator counts as approved.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner_acc: AccountId, operator_acc: AccountId) -> bool {
+            match self.operator_approval.get(&(owner_acc, operator_acc)) {
+                // a lapsed timed grant is still an explicit choice, so the default operator stays out
+                Some(approved_flag) => approved_flag && !self.operator_expired(owner_acc, operator_acc),
+                None => self.default_operator_opt == Some(operator_acc),
+            }
+        }
+
+        /// Pre-approve `operator_opt` (a trusted marketplace) as operator for every owner
+        /// who hasn't opted out with `set_approval_for_all(op, false)`; `None` clears it.
+        ///
+        /// Security: the default operator can move every token of every holder who has
+        /// not opted out, including holders who never interacted with it, and changing it
+        /// re-points that power at once. Only set a contract you fully trust. It is not
+        /// listed by `operators_of`/`approved_operators` and is not cleared by
+        /// `clear_operators_on_transfer`; `revoke_all_operators` does opt out of it.
+        #[ink(message)]
+        pub fn set_default_operator(&mut self, operator_opt: Option<AccountId>) -> Result<()> {
+            self.only_owner()?;
+            self.default_operator_opt = operator_opt;
+            self.env().emit_event(DefaultOperatorSet { operator_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn default_operator(&self) -> Option<AccountId> {
+            self.default_operator_opt
+        }
+
+        /// `is_approved_for_all(owner_acc, op)` for each operator, in input order. Only the
+        /// first `MAX_OPERATOR_QUERY` operators are checked.
+        #[ink(message)]
+        pub fn is_approved_for_all_many(&self, owner_acc: AccountId, operators_vec: Vec<AccountId>) -> Vec<bool> {
+            operators_vec
+                .into_iter()
+                .take(MAX_OPERATOR_QUERY)
+                .map(|operator_acc| self.is_approved_for_all(owner_acc, operator_acc))
+                .collect()
+        }
+
+        // -------- marketplace --------
+
+        /// Configure Moo-priced sales: the Moo token, where fees go, and the fee in bps.
+        #[ink(message)]
+        pub fn set_market_config(
+            &mut self,
+            moo_acc_opt: Option<AccountId>,
+            treasury_acc: AccountId,
+            fee_bps: u16,
+        ) -> Result<()> {
+            self.only_owner()?;
+            if fee_bps > 10_000 {
+                return Err(Error::InvalidFee)
+            }
+            self.moo_acc_opt = moo_acc_opt;
+            self.treasury_acc = treasury_acc;
+            self.market_fee_bps = fee_bps;
+            self.env().emit_event(MarketConfigSet { moo_acc_opt, treasury_acc, fee_bps });
+            Ok(())
+        }
+
+        /// Collection-wide royalty `(recipient, bps)`, or `None` for no royalty.
+        #[ink(message)]
+        pub fn set_default_royalty(&mut self, royalty_opt: Option<(AccountId, u16)>) -> Result<()> {
+            self.only_owner()?;
+            if matches!(royalty_opt, Some((_, royalty_bps)) if royalty_bps > 10_000) {
+                return Err(Error::InvalidFee)
+            }
+            self.default_royalty_opt = royalty_opt;
+            self.env().emit_event(DefaultRoyaltySet { royalty_opt });
+            Ok(())
+        }
+
+        /// Per-token royalty that takes precedence over the default; dropped on burn.
+        #[ink(message)]
+        pub fn set_token_royalty(&mut self, token_id: TokenId, recipient_acc: AccountId, royalty_bps: u16) -> Result<()> {
+            self.only_owner()?;
+            if royalty_bps > 10_000 {
+                return Err(Error::InvalidFee)
+            }
+            if self.owner_by_id.get(&token_id).is_none() {
+                return Err(Error::TokenMissing)
+            }
+            self.token_royalty.insert(&token_id, &(recipient_acc, royalty_bps));
+            self.env().emit_event(TokenRoyaltySet { token_id, recipient_acc, royalty_bps });
+            Ok(())
+        }
+
+        /// `(recipient, amount)` owed on a sale of `token_id` at `sale_price`: the token's
+        /// override if set, else the collection default, else `None`.
+        #[ink(message)]
+        pub fn royalty_info(&self, token_id: TokenId, sale_price: Balance) -> Option<(AccountId, Balance)> {
+            let (recipient_acc, royalty_bps) = self.token_royalty.get(&token_id).or(self.default_royalty_opt)?;
+            let royalty_val = sale_price.saturating_mul(Balance::from(royalty_bps)) / 10_000;
+            Some((recipient_acc, royalty_val))
+        }
+
+        /// List a token you own for `price_val` Moo. Any transfer or burn drops the listing.
+        #[ink(message)]
+        pub fn list_for_moo(&mut self, token_id: TokenId, price_val: Balance) -> Result<()> {
+            self.when_not_paused()?;
+            if price_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            let seller_acc = self.owner_by_id.get(&token_id).ok_or(Error::TokenMissing)?;
+            if seller_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            self.listings.insert(&token_id, &(seller_acc, price_val));
+            self.env().emit_event(Listed { seller_acc, token_id, price_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unlist(&mut self, token_id: TokenId) -> Result<()> {
+            let (seller_acc, _) = self.listings.get(&token_id).ok_or(Error::NotListed)?;
+            if seller_acc != self.env().caller() {
+                return Err(Error::NotOwner)
+            }
+            self.listings.remove(&token_id);
+            self.env().emit_event(Unlisted { token_id });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn listing_of(&self, token_id: TokenId) -> Option<(AccountId, Balance)> {
+            self.listings.get(&token_id)
+        }
+
+        /// Pay out the caller's accrued marketplace royalties in Moo. The balance is zeroed
+        /// before the transfer; a failed transfer reverts the call and leaves it owed.
+        #[ink(message)]
+        pub fn withdraw_royalties(&mut self) -> Result<()> {
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            let recipient_acc = self.env().caller();
+            let amount_val = self.royalty_owed_of(recipient_acc);
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.royalty_owed.remove(&recipient_acc);
+            let mut moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            let call_res = moo_ref.call_mut().transfer(recipient_acc, amount_val).try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::WithdrawFailed)
+            }
+            self.env().emit_event(RoyaltyWithdrawn { recipient_acc, amount_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn royalty_owed_of(&self, acc: AccountId) -> Balance {
+            self.royalty_owed.get(&acc).unwrap_or(0)
+        }
+
+        /// After a marketplace sale the token can't be sold again for `cooldown_blocks`
+        /// blocks (`ResaleCooldown`), to discourage wash-trading loops. With `direct_flag`
+        /// set plain transfers wait out the cooldown too. Zero disables it.
+        #[ink(message)]
+        pub fn set_resale_cooldown(&mut self, cooldown_blocks: u32, direct_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.resale_cooldown_blocks = cooldown_blocks;
+            self.resale_cooldown_direct_flag = direct_flag;
+            self.env().emit_event(ResaleCooldownSet { cooldown_blocks, direct_flag });
+            Ok(())
+        }
+
+        /// `(resale_cooldown_blocks, resale_cooldown_direct_flag)`.
+        #[ink(message)]
+        pub fn resale_cooldown(&self) -> (u32, bool) {
+            (self.resale_cooldown_blocks, self.resale_cooldown_direct_flag)
+        }
+
+        /// Block of the token's last marketplace sale, if it was ever sold.
+        #[ink(message)]
+        pub fn last_sale_block_of(&self, token_id: TokenId) -> Option<u32> {
+            self.last_sale_block.get(&token_id)
+        }
+
+        /// Buy a listed token with Moo. The buyer must have approved this contract for the
+        /// price; the pulls (seller share, treasury fee, then any `royalty_info` royalty into
+        /// this contract) happen before any local state changes, and a failure in any of them
+        /// reverts the whole call. Royalties come out of the seller's share and are credited
+        /// to the recipient's `royalty_owed_of` rather than pushed; the credit is the Moo that
+        /// actually arrived, since Moo's transfer fee, auto-burn and creator fee can skim it.
+        /// List this contract with Moo's `set_fee_exempt` so royalties and their payouts go
+        /// through untaxed and its balance is never held by Moo's receive cooldown.
+        #[ink(message)]
+        pub fn buy_with_moo(&mut self, token_id: TokenId) -> Result<()> {
+            self.when_not_paused()?;
+            let moo_acc = self.moo_acc_opt.ok_or(Error::MarketUnconfigured)?;
+            let (seller_acc, price_val) = self.listings.get(&token_id).ok_or(Error::NotListed)?;
+            if self.owner_by_id.get(&token_id) != Some(seller_acc) {
+                return Err(Error::NotListed)
+            }
+            let buyer_acc = self.env().caller();
+            if buyer_acc == seller_acc {
+                return Err(Error::SameAccount)
+            }
+            self.when_resale_cooled(token_id)?;
+
+            let fee_val = price_val
+                .checked_mul(Balance::from(self.market_fee_bps))
+                .ok_or(Error::Overflow)?
+                / 10_000;
+            let royalty_opt = self
+                .royalty_info(token_id, price_val)
+                .map(|(recipient_acc, royalty_val)| (recipient_acc, min(royalty_val, price_val - fee_val)));
+            let royalty_val = royalty_opt.map_or(0, |(_, royalty_val)| royalty_val);
+            let seller_val = price_val - fee_val - royalty_val;
+            self.pull_moo(moo_acc, buyer_acc, seller_acc, seller_val)?;
+            self.pull_moo(moo_acc, buyer_acc, self.treasury_acc, fee_val)?;
+            let received_val = self.pull_moo_in(moo_acc, buyer_acc, royalty_val)?;
+
+            if let Some((recipient_acc, _)) = royalty_opt {
+                if received_val != 0 {
+                    let owed_val = self.royalty_owed_of(recipient_acc);
+                    self.royalty_owed
+                        .insert(&recipient_acc, &owed_val.checked_add(received_val).ok_or(Error::Overflow)?);
+                    self.royalties_collected_val =
+                        self.royalties_collected_val.checked_add(received_val).ok_or(Error::Overflow)?;
+                    self.env().emit_event(RoyaltyAccrued { recipient_acc, token_id, amount_val: received_val });
+                }
+            }
+            self.move_token(seller_acc, buyer_acc, token_id)?;
+            self.last_sale_block.insert(&token_id, &self.env().block_number());
+            self.env().emit_event(SoldForMoo { seller_acc, buyer_acc, token_id, price_val, fee_val });
+            Ok(())
+        }
+
+        // -------- treasury --------
+
+        /// Native balance that `withdraw`/`withdraw_all` never dip below. Defaults to the
+        /// chain's existential deposit so a sweep cannot get the contract reaped.
+        #[ink(message)]
+        pub fn set_min_reserve_balance(&mut self, min_reserve_balance: Balance) -> Result<()> {
+            self.only_owner()?;
+            self.min_reserve_balance = min_reserve_balance;
+            self.env().emit_event(MinReserveSet { min_reserve_balance });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_reserve_balance(&self) -> Balance {
+            self.min_reserve_balance
+        }
+
+        #[ink(message)]
+        pub fn withdraw(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.only_owner()?;
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            if let Some(max_val) = self.max_single_withdrawal {
+                if amount_val > max_val {
+                    return Err(Error::WithdrawalTooLarge)
+                }
+            }
+            if amount_val > self.withdrawable_balance() {
+                return Err(Error::ReserveFloor)
+            }
+            self.send_native(to_acc, amount_val)
+        }
+
+        /// Sweep everything above `min_reserve_balance`, or at most `max_single_withdrawal`
+        /// when a cap is set; repeat the call to drain the rest.
+        #[ink(message)]
+        pub fn withdraw_all(&mut self, to_acc: AccountId) -> Result<()> {
+            self.only_owner()?;
+            let available_val = self.withdrawable_balance();
+            let amount_val = self.max_single_withdrawal.map_or(available_val, |max_val| min(available_val, max_val));
+            if amount_val == 0 {
+                return Err(Error::ReserveFloor)
+            }
+            self.send_native(to_acc, amount_val)
+        }
+
+        /// Guardrail against fat-finger sweeps by any admin: caps what one `withdraw` or
+        /// `withdraw_all` call can move. Independent of `min_reserve_balance`; `None` lifts it.
+        #[ink(message)]
+        pub fn set_max_single_withdrawal(&mut self, max_withdrawal_opt: Option<Balance>) -> Result<()> {
+            self.only_owner()?;
+            self.max_single_withdrawal = max_withdrawal_opt;
+            self.env().emit_event(MaxWithdrawalSet { max_withdrawal_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_single_withdrawal(&self) -> Option<Balance> {
+            self.max_single_withdrawal
+        }
+
+        /// Queue sale proceeds instead of pushing them: `mint_public` revenue shares are
+        /// credited to each payee's `pending_withdrawal_of` for `withdraw_proceeds`, so a
+        /// non-payable payee contract can't block sales. Queued funds are excluded from the
+        /// owner's `withdraw`/`withdraw_all`.
+        #[ink(message)]
+        pub fn set_queue_proceeds(&mut self, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.queue_proceeds_flag = enabled_flag;
+            self.env().emit_event(ProceedsQueueSet { enabled_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn queue_proceeds(&self) -> bool {
+            self.queue_proceeds_flag
+        }
+
+        /// Pay out the caller's queued proceeds. The balance is zeroed before the native
+        /// transfer; a failed transfer reverts the call and leaves it owed.
+        #[ink(message)]
+        pub fn withdraw_proceeds(&mut self) -> Result<()> {
+            let payee_acc = self.env().caller();
+            let amount_val = self.pending_withdrawal_of(payee_acc);
+            if amount_val == 0 {
+                return Err(Error::AmountZero)
+            }
+            self.pending_withdrawals.remove(&payee_acc);
+            self.total_pending = self.total_pending.saturating_sub(amount_val);
+            self.env().transfer(payee_acc, amount_val).map_err(|_| Error::WithdrawFailed)?;
+            self.env().emit_event(ProceedsWithdrawn { payee_acc, amount_val });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn pending_withdrawal_of(&self, acc: AccountId) -> Balance {
+            self.pending_withdrawals.get(&acc).unwrap_or(0)
+        }
+
+        // -------- queries --------
+
+        /// Set the metadata URI, e.g. `ipfs://CID/` or a template like `ipfs://CID/{id}.json`.
+        #[ink(message)]
+        pub fn set_base_uri(&mut self, base_uri: String) -> Result<()> {
+            self.only_owner()?;
+            self.base_uri = base_uri.clone();
+            self.env().emit_event(BaseUriSet { base_uri });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn base_uri(&self) -> String {
+            self.base_uri.clone()
+        }
+
+        /// Mark the collection revealed (typically right after pointing `set_base_uri` at
+        /// the final metadata). One-way; lifts the reveal lock.
+        #[ink(message)]
+        pub fn reveal(&mut self) -> Result<()> {
+            self.only_owner()?;
+            if !self.revealed_flag {
+                self.revealed_flag = true;
+                self.env().emit_event(Revealed { by_acc: self.env().caller() });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_revealed(&self) -> bool {
+            self.revealed_flag
+        }
+
+        /// With `locked_flag` set, no token can change hands (`NotRevealed`) until `reveal`,
+        /// so nobody flips based on mint order. Mints and burns are unaffected.
+        #[ink(message)]
+        pub fn set_lock_until_reveal(&mut self, locked_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.lock_until_reveal_flag = locked_flag;
+            self.env().emit_event(RevealLockSet { locked_flag });
+            Ok(())
+        }
+
+        /// Metadata URI shown for every unrevealed token while non-empty (the default, empty,
+        /// shows real metadata for all tokens).
+        #[ink(message)]
+        pub fn set_placeholder_uri(&mut self, placeholder_uri: String) -> Result<()> {
+            self.only_owner()?;
+            self.placeholder_uri = placeholder_uri.clone();
+            self.env().emit_event(PlaceholderUriSet { placeholder_uri });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn placeholder_uri(&self) -> String {
+            self.placeholder_uri.clone()
+        }
+
+        /// Staged reveal: tokens with id `<= token_id` get their real `token_uri`. The
+        /// high-water mark only moves up (`RevealBackwards`); `reveal` covers every token
+        /// at once and is what lifts the reveal transfer lock.
+        #[ink(message)]
+        pub fn reveal_up_to(&mut self, token_id: TokenId) -> Result<()> {
+            self.only_owner()?;
+            let revealed_up_to = token_id.checked_add(1).ok_or(Error::Overflow)?;
+            if revealed_up_to <= self.revealed_up_to {
+                return Err(Error::RevealBackwards)
+            }
+            self.revealed_up_to = revealed_up_to;
+            self.env().emit_event(RevealedUpTo { token_id });
+            Ok(())
+        }
+
+        /// Whether `token_id` shows real metadata (revealed globally or within the staged
+        /// range).
+        #[ink(message)]
+        pub fn is_token_revealed(&self, token_id: TokenId) -> bool {
+            self.revealed_flag || token_id < self.revealed_up_to
+        }
+
+        /// `true` while transfers are blocked waiting for `reveal`.
+        #[ink(message)]
+        pub fn transfers_reveal_locked(&self) -> bool {
+            self.lock_until_reveal_flag && !self.revealed_flag
+        }
+
+        /// Emit `NFMintedWithUri` (URI cut to `MAX_EVENT_URI_LEN` bytes) instead of the lean
+        /// `NFMinted` on every mint, sparing indexers a `token_uri` call per token.
+        #[ink(message)]
+        pub fn set_emit_uri_on_mint(&mut self, enabled_flag: bool) -> Result<()> {
+            self.only_owner()?;
+            self.emit_uri_on_mint = enabled_flag;
+            self.env().emit_event(EmitUriOnMintSet { enabled_flag });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn emit_uri_on_mint(&self) -> bool {
+            self.emit_uri_on_mint
+        }
+
+        /// Every `{id}` in the base URI is replaced by the decimal token id; without a
+        /// placeholder the id is appended. Unrevealed tokens get `placeholder_uri` as-is
+        /// while one is set. `None` for tokens that don't exist.
+        #[ink(message)]
+        pub fn token_uri(&self, token_id: TokenId) -> Option<String> {
+            self.owner_by_id.get(&token_id)?;
+            if !self.placeholder_uri.is_empty() && !self.is_token_revealed(token_id) {
+                return Some(self.placeholder_uri.clone())
+            }
+            let id_str = token_id.to_string();
+            if self.base_uri.contains("{id}") {
+                Some(self.base_uri.replace("{id}", &id_str))
+            } else {
+                let mut uri_str = self.base_uri.clone();
+                uri_str.push_str(&id_str);
+                Some(uri_str)
+            }
+        }
+
+        /// Cap how many tokens sale phase `phase` (`SALE_PUBLIC` for `mint_public`,
+        /// `SALE_MOO` for `mint_paying_moo`) may mint in total, so one phase can't eat
+        /// another's allocation (`PhaseCapReached`). `None` leaves only `max_supply`.
+        #[ink(message)]
+        pub fn set_phase_cap(&mut self, phase: u8, cap_opt: Option<u32>) -> Result<()> {
+            self.only_owner()?;
+            match cap_opt {
+                Some(cap_cnt) => {
+                    self.phase_cap.insert(&phase, &cap_cnt);
+                }
+                None => self.phase_cap.remove(&phase),
+            }
+            self.env().emit_event(PhaseCapSet { phase, cap_opt });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn phase_cap_of(&self, phase: u8) -> Option<u32> {
+            self.phase_cap.get(&phase)
+        }
+
+        /// Tokens minted in `phase`, counted whether or not it is capped.
+        #[ink(message)]
+        pub fn phase_minted_of(&self, phase: u8) -> u32 {
+            self.phase_minted.get(&phase).unwrap_or(0)
+        }
+
+        /// Supply, cap, mint/burn totals, sale phase and pause state in one read.
+        #[ink(message)]
+        pub fn collection_stats(&self) -> CollectionStats {
+            let mut sale_phase = 0;
+            if self.price_opt.is_some() {
+                sale_phase |= SALE_PUBLIC;
+            }
+            if self.price_in_moo_opt.is_some() {
+                sale_phase |= SALE_MOO;
+            }
+            CollectionStats {
+                total_supply: self.supply_cnt,
+                max_supply: self.max_supply_opt,
+                minted_cnt: self.minted_total(),
+                burned_cnt: self.burned_cnt,
+                next_id: self.next_id,
+                sale_phase,
+                paused: self.paused_flag,
+            }
+        }
+
+        /// Owner, approval, URI, lock status and tier in one read. A missing token yields
+        /// all `None`/`false` rather than an error.
+        #[ink(message)]
+        pub fn token_detail(&self, token_id: TokenId) -> TokenDetail {
+            let owner = self.owner_by_id.get(&token_id);
+            if owner.is_none() {
+                return TokenDetail { owner: None, approved: None, uri: None, locked: false, tier: None }
+            }
+            TokenDetail {
+                owner,
+                approved: self.token_approval.get(&token_id),
+                uri: self.token_uri(token_id),
+                locked: self.is_token_frozen(token_id)
+                    || self.env().block_timestamp() < self.unlock_time_of(token_id)
+                    || self.fractionalized.contains(&token_id),
+                tier: self.token_tier.get(&token_id),
+            }
+        }
+
+        /// Standards discovery for marketplaces and aggregators. `INTERFACE_DISCOVERY`,
+        /// `INTERFACE_PSP34` and `INTERFACE_PSP34_ENUMERABLE` are always on;
+        /// `INTERFACE_PSP34_METADATA` once a base URI is set and `INTERFACE_ROYALTY` while
+        /// a collection default royalty is configured.
+        #[ink(message)]
+        pub fn supports_interface(&self, interface_id: [u8; 4]) -> bool {
+            match interface_id {
+                INTERFACE_DISCOVERY | INTERFACE_PSP34 | INTERFACE_PSP34_ENUMERABLE => true,
+                INTERFACE_PSP34_METADATA => !self.base_uri.is_empty(),
+                INTERFACE_ROYALTY => self.default_royalty_opt.is_some(),
+                _ => false,
+            }
+        }
+
+        /// Who owns this token?
+        #[ink(message)]
+        pub fn owner_of(&self, token_id: TokenId) -> Option<AccountId> {
+            self.owner_by_id.get(&token_id)
+        }
+
+        /// How many tokens does this account own?
+        #[ink(message)]
+        pub fn balance_of(&self, owner_acc: AccountId) -> u32 {
+            self.owned_count.get(&owner_acc).unwrap_or(0)
+        }
+
+        /// Owned tokens that can't move right now: admin-frozen ones, plus vesting tokens
+        /// from `mint_locked`. Both counters follow the token through every move and burn.
+        /// Vesting is tracked per account window, so every token locked
+        /// in the current window counts until the window's latest unlock, even if some of
+        /// them unlocked earlier. Never exceeds `balance_of`.
+        #[ink(message)]
+        pub fn locked_balance_of(&self, owner_acc: AccountId) -> u32 {
+            let frozen_cnt = self.frozen_cnt.get(&owner_acc).unwrap_or(0);
+            let vesting_cnt = match self.vesting_window.get(&owner_acc) {
+                Some((_, until_ts)) if self.env().block_timestamp() < until_ts => {
+                    self.vesting_cnt.get(&owner_acc).unwrap_or(0)
+                }
+                _ => 0,
+            };
+            min(frozen_cnt.saturating_add(vesting_cnt), self.balance_of(owner_acc))
+        }
+
+        /// `balance_of` minus `locked_balance_of`.
+        #[ink(message)]
+        pub fn transferable_balance_of(&self, owner_acc: AccountId) -> u32 {
+            self.balance_of(owner_acc) - self.locked_balance_of(owner_acc)
+        }
+
+        /// Paginated list of token ids owned by `owner_acc`.
+        #[ink(message)]
+        pub fn tokens_of(&self, owner_acc: AccountId, start_index: u32, limit_cnt: u32) -> Vec<TokenId> {
+            let count_val = self.balance_of(owner_acc);
+            if start_index >= count_val || limit_cnt == 0 {
+                return Vec::new()
+            }
+            let end_index = min(count_val, start_index.saturating_add(limit_cnt));
+            let mut list_vec: Vec<TokenId> = Vec::new();
+            let mut index_val = start_index;
+            while index_val < end_index {
+                if let Some(token_id) = self.tokens_by_owner.get(&(owner_acc, index_val)) {
+                    list_vec.push(token_id);
+                }
+                index_val += 1;
+            }
+            list_vec
+        }
+
+        /// First `limit_per` tokens of each owner, in input order. Only the first
+        /// `MAX_MANY_OWNERS` owners are read and `limit_per` is clamped to `MAX_MANY_PER_OWNER`.
+        #[ink(message)]
+        pub fn tokens_of_many(&self, owners_vec: Vec<AccountId>, limit_per: u32) -> Vec<(AccountId, Vec<TokenId>)> {
+            let limit_cnt = min(limit_per, MAX_MANY_PER_OWNER);
+            owners_vec
+                .into_iter()
+                .take(MAX_MANY_OWNERS)
+                .map(|owner_acc| (owner_acc, self.tokens_of(owner_acc, 0, limit_cnt)))
+                .collect()
+        }
+
+        /// Start a new holder snapshot epoch and return it. O(1): each account's count is
+        /// checkpointed the next time its holdings change.
+        #[ink(message)]
+        pub fn snapshot_holders(&mut self) -> Result<u32> {
+            self.only_owner()?;
+            self.holders_epoch = self.holders_epoch.checked_add(1).ok_or(Error::Overflow)?;
+            let epoch = self.holders_epoch;
+            self.env().emit_event(HoldersSnapshot { epoch });
+            Ok(epoch)
+        }
+
+        #[ink(message)]
+        pub fn current_holders_epoch(&self) -> u32 {
+            self.holders_epoch
+        }
+
+        /// `balance_of(acc)` as of `snapshot_holders` returning `epoch`.
+        #[ink(message)]
+        pub fn holdings_at(&self, acc: AccountId, epoch: u32) -> Result<u32> {
+            if epoch == 0 || epoch > self.holders_epoch {
+                return Err(Error::InvalidSnapshot)
+            }
+            Ok(self.holdings_checkpoint_at(acc, epoch).unwrap_or_else(|| self.balance_of(acc)))
+        }
+
+        /// Every token id owned by `owner_acc`, unpaginated. Holdings above
+        /// `MAX_ALL_OWNED` (500) are rejected with `Overflow`; use `tokens_of` for those.
+        #[ink(message)]
+        pub fn all_owned_tokens(&self, owner_acc: AccountId) -> Result<Vec<TokenId>> {
+            let count_val = self.balance_of(owner_acc);
+            if count_val > MAX_ALL_OWNED {
+                return Err(Error::Overflow)
+            }
+            Ok(self.tokens_of(owner_acc, 0, count_val))
+        }
+
+        // -------- internals: roles --------
+
+        fn set_role(&mut self, role_u8: u8, acc: AccountId, enabled_flag: bool) -> Result<()> {
+            if role_u8 > ROLE_BURNER {
+                return Err(Error::InvalidRole)
+            }
+            self.roles.insert(&(role_u8, acc), &enabled_flag);
+            let by_acc = self.env().caller();
+            if enabled_flag {
+                self.env().emit_event(RoleGranted { role_u8, acc, by_acc });
+            } else {
+                self.env().emit_event(RoleRevoked { role_u8, acc, by_acc });
+            }
+            Ok(())
+        }
+
+        // -------- internals: minting --------
+
+        /// Shared gate for minter batches: pause, role, and the summed count bound.
+        fn start_minter_batch(&self, recipients_vec: &[(AccountId, u32)]) -> Result<()> {
+            self.when_not_paused()?;
+            if !self.has_role(ROLE_MINTER, self.env().caller()) {
+                return Err(Error::Unauthorized)
+            }
+            let mut total_cnt: u32 = 0;
+            for (_, amount_cnt) in recipients_vec {
+                total_cnt = total_cnt.checked_add(*amount_cnt).ok_or(Error::Overflow)?;
+            }
+            if total_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            if total_cnt > MAX_PER_CALL {
+                return Err(Error::Overflow)
+            }
+            Ok(())
+        }
+
+        fn raise_price(&mut self, amount_cnt: u32) -> Result<()> {
+            if !self.price_increase_per_mint_flag || self.price_step == 0 {
+                return Ok(())
+            }
+            if let Some(price_val) = self.price_opt {
+                let rise_val = self.price_step.checked_mul(Balance::from(amount_cnt)).ok_or(Error::Overflow)?;
+                let price_val = price_val.checked_add(rise_val).ok_or(Error::Overflow)?;
+                self.price_opt = Some(price_val);
+                self.env().emit_event(PriceUpdated { price_val });
+            }
+            Ok(())
+        }
+
+        fn check_wallet_cap(&self, to_acc: AccountId, amount_cnt: u32) -> Result<()> {
+            if let Some(max_per_wallet) = self.max_per_wallet_opt {
+                let new_bal = self.balance_of(to_acc).checked_add(amount_cnt).ok_or(Error::Overflow)?;
+                if new_bal > max_per_wallet {
+                    return Err(Error::WalletCapReached)
+                }
+            }
+            Ok(())
+        }
+
+        /// Tokens ever minted: live supply plus burns.
+        fn minted_total(&self) -> u128 {
+            self.supply_cnt.saturating_add(self.burned_cnt)
+        }
+
+        /// Fail fast: the whole batch must fit under the cap.
+        fn check_supply_room(&self, amount_cnt: u32) -> Result<()> {
+            if let Some(max_supply_val) = self.max_supply_opt {
+                let new_supply = self.supply_cnt.checked_add(u128::from(amount_cnt)).ok_or(Error::Overflow)?;
+                if new_supply > max_supply_val {
+                    return Err(Error::MaxSupplyReached)
+                }
+            }
+            Ok(())
+        }
+
+        /// Everything `mint_n` checks before minting.
+        fn check_mint_n(&self, minter_acc: AccountId, amount_cnt: u32) -> Result<()> {
+            self.when_not_paused()?;
+            if amount_cnt == 0 {
+                return Err(Error::AmountZero)
+            }
+            if !self.has_role(ROLE_MINTER, minter_acc) {
+                return Err(Error::Unauthorized)
+            }
+            self.check_wallet_cap(minter_acc, amount_cnt)?;
+            self.check_batch(minter_acc, amount_cnt)
+        }
+
+        fn check_batch(&self, to_acc: AccountId, amount_cnt: u32) -> Result<()> {
+            if amount_cnt > MAX_PER_CALL {
+                return Err(Error::Overflow)
+            }
+            if self.block_contract_recipients && self.env().is_contract(&to_acc) {
+                return Err(Error::ContractRecipient)
+            }
+            self.check_supply_room(amount_cnt)
+        }
+
+        fn mint_batch(&mut self, to_acc: AccountId, amount_cnt: u32) -> Result<()> {
+            self.check_batch(to_acc, amount_cnt)?;
+            for _ in 0..amount_cnt {
+                self.mint_one(to_acc)?;
+            }
+            Ok(())
+        }
+
+        fn mint_one(&mut self, to_acc: AccountId) -> Result<TokenId> {
+            if let Some(max_supply_val) = self.max_supply_opt {
+                if self.supply_cnt >= max_supply_val {
+                    return Err(Error::MaxSupplyReached)
+                }
+            }
+
+            // skip ids already taken (or taken and burned) through `mint_with_id`
+            let mut token_id = self.next_token_id()?;
+            while self.id_taken(token_id) {
+                token_id = self.next_token_id()?;
+            }
+            self.place_minted(to_acc, token_id)?;
+            Ok(token_id)
+        }
+
+        /// True if `token_id` is live or was ever burned.
+        fn id_taken(&self, token_id: TokenId) -> bool {
+            self.owner_by_id.contains(&token_id) || self.burned_ids.contains(&token_id)
+        }
+
+        fn place_minted(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_opted_in(to_acc, true)?;
+            self.owner_by_id.insert(&token_id, &to_acc);
+            self.add_token_to_owner(to_acc, token_id)?;
+            self.acquired_block.insert(&token_id, &self.env().block_number());
+            let minted_cnt = self.minted_count_of(to_acc).checked_add(1).ok_or(Error::Overflow)?;
+            self.minted_by.insert(&to_acc, &minted_cnt);
+            self.supply_cnt = self.supply_cnt.checked_add(1).ok_or(Error::Overflow)?;
+            if self.emit_uri_on_mint {
+                let mut uri = self.token_uri(token_id).unwrap_or_default();
+                if uri.len() > MAX_EVENT_URI_LEN {
+                    let mut cut_len = MAX_EVENT_URI_LEN;
+                    while !uri.is_char_boundary(cut_len) {
+                        cut_len -= 1;
+                    }
+                    uri.truncate(cut_len);
+                }
+                self.env().emit_event(NFMintedWithUri { to_acc, token_id, uri });
+            } else {
+                self.env().emit_event(NFMinted { to_acc, token_id });
+            }
+            Ok(())
+        }
+
+        // -------- internals: id assignment --------
+
+        /// Sequential ids by default. With `shuffle_flag` this is a lazy Fisher–Yates draw
+        /// over `[0, max_supply)`: pool slot `i` holds `remaining_ids[i]`, or `i` if unset,
+        /// and the drawn slot is refilled from the last one so ids never repeat.
+        /// The seed is block data, which block authors can bias — it only hides mint
+        /// order from casual snipers and should be combined with a delayed reveal.
+        fn next_token_id(&mut self) -> Result<TokenId> {
+            let drawn_cnt = self.next_id;
+            self.next_id = self.next_id.checked_add(1).ok_or(Error::Overflow)?;
+            if !self.shuffle_flag {
+                return Ok(drawn_cnt)
+            }
+
+            let max_supply_val = self.max_supply_opt.ok_or(Error::SupplyUncapped)?;
+            let pool_len = max_supply_val.saturating_sub(drawn_cnt);
+            if pool_len == 0 {
+                return Err(Error::MaxSupplyReached)
+            }
+            let seed_hash = self.env().hash_encoded::<Blake2x256, _>(&(
+                self.env().block_timestamp(),
+                self.env().block_number(),
+                self.env().caller(),
+                drawn_cnt,
+            ));
+            let mut seed_bytes = [0u8; 16];
+            seed_bytes.copy_from_slice(&seed_hash[..16]);
+            let pick_index = u128::from_le_bytes(seed_bytes) % pool_len;
+
+            let last_index = pool_len - 1;
+            let token_id = self.remaining_ids.get(&pick_index).unwrap_or(pick_index);
+            if pick_index != last_index {
+                let last_id = self.remaining_ids.get(&last_index).unwrap_or(last_index);
+                self.remaining_ids.insert(&pick_index, &last_id);
+            }
+            self.remaining_ids.remove(&last_index);
+            Ok(token_id)
+        }
+
+        // -------- internals: owner sets management --------
+
+        fn move_token(&mut self, from_acc: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.relocate_token(from_acc, to_acc, token_id)?;
+            self.env().emit_event(NFTransferred { from_acc, to_acc, token_id });
+            Ok(())
+        }
+
+        /// `move_token` without the event, for callers that emit their own.
+        fn relocate_token(&mut self, from_acc: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.when_transfers_open(from_acc, to_acc)?;
+            self.when_opted_in(to_acc, false)?;
+            self.when_token_movable(token_id)?;
+            self.run_transfer_hook(from_acc, to_acc, token_id)?;
+            self.shift_owner(from_acc, to_acc, token_id)?;
+            self.note_changed_hands(from_acc, token_id)
+        }
+
+        /// Take `token_id` from `from_acc` into this contract's custody (wrapping, pending
+        /// transfers). The sender's usual checks apply except opt-in, which the contract never gives.
+        /// Custody isn't a change of hands: the hold clock, `transfer_count` and the
+        /// operator snapshot stay as they were.
+        fn enter_custody(&mut self, from_acc: AccountId, token_id: TokenId) -> Result<()> {
+            let contract_acc = self.env().account_id();
+            self.when_transfers_open(from_acc, contract_acc)?;
+            self.when_token_movable(token_id)?;
+            self.run_transfer_hook(from_acc, contract_acc, token_id)?;
+            self.shift_owner(from_acc, contract_acc, token_id)?;
+            self.env().emit_event(NFTransferred { from_acc, to_acc: contract_acc, token_id });
+            Ok(())
+        }
+
+        /// Release `token_id` from custody to `to_acc`; the caller drops its custody record.
+        /// The move was vetted on the way in, so the hold period doesn't apply here.
+        /// `origin_acc` is whoever put it in custody: handing it to anyone else (an accepted
+        /// pending transfer) is a change of hands and needs `to_acc` opted in.
+        fn leave_custody(&mut self, origin_acc: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            let contract_acc = self.env().account_id();
+            self.when_transfers_open(contract_acc, to_acc)?;
+            self.when_not_frozen(token_id)?;
+            let changed_hands_flag = origin_acc != to_acc;
+            if changed_hands_flag {
+                self.when_opted_in(to_acc, false)?;
+            }
+            self.run_transfer_hook(contract_acc, to_acc, token_id)?;
+            self.shift_owner(contract_acc, to_acc, token_id)?;
+            if changed_hands_flag {
+                self.note_changed_hands(origin_acc, token_id)?;
+            }
+            self.env().emit_event(NFTransferred { from_acc: contract_acc, to_acc, token_id });
+            Ok(())
+        }
+
+        /// Ownership bookkeeping shared by every move: approvals, vault record, owner sets
+        /// and the per-owner lock counters behind `locked_balance_of`.
+        fn shift_owner(&mut self, from_acc: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            self.clear_token_approval(token_id);
+            // any move out of the vault ends the fractionalization
+            self.clear_fractionalization(token_id);
+            self.release_vesting(from_acc, token_id);
+            if self.is_token_frozen(token_id) {
+                self.uncount_frozen(from_acc);
+                let frozen_cnt = self.frozen_cnt.get(&to_acc).unwrap_or(0);
+                self.frozen_cnt.insert(&to_acc, &frozen_cnt.checked_add(1).ok_or(Error::Overflow)?);
+            }
+            self.remove_token_from_owner(from_acc, token_id)?;
+            self.owner_by_id.insert(&token_id, &to_acc);
+            self.add_token_to_owner(to_acc, token_id)
+        }
+
+        /// `token_id` left `from_acc` for a new holder: restart the hold clock, count the
+        /// move and snapshot `from_acc`'s operators if enabled.
+        fn note_changed_hands(&mut self, from_acc: AccountId, token_id: TokenId) -> Result<()> {
+            if self.clear_operators_on_transfer {
+                self.token_operator_floor.insert(&(token_id, from_acc), &self.operator_grant_seq);
+            }
+            self.acquired_block.insert(&token_id, &self.env().block_number());
+            let moves_cnt = self.transfer_count_of(token_id).checked_add(1).ok_or(Error::Overflow)?;
+            self.transfer_count.insert(&token_id, &moves_cnt);
+            Ok(())
+        }
+
+        /// Ask the hook (if any) to approve a move. Runs before any state change; the lock
+        /// rejects every move attempted while the hook is executing.
+        fn run_transfer_hook(&mut self, from_acc: AccountId, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            if self.hook_lock.get().unwrap_or(false) {
+                return Err(Error::Reentrant)
+            }
+            let Some(hook_acc) = self.transfer_hook_opt else {
+                return Ok(())
+            };
+            self.hook_lock.set(&true);
+            let call_res = build_call::<DefaultEnvironment>()
+                .call(hook_acc)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("before_transfer")))
+                        .push_arg(from_acc)
+                        .push_arg(to_acc)
+                        .push_arg(token_id),
+                )
+                .returns::<core::result::Result<(), u8>>()
+                .try_invoke();
+            self.hook_lock.set(&false);
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::HookRejected)
+            }
+            Ok(())
+        }
+
+        /// `owned_index` points at a slot below `owned_count` that holds `token_id`.
+        fn enumeration_intact(&self, owner_acc: AccountId, token_id: TokenId) -> bool {
+            match self.owned_index.get(&token_id) {
+                Some(index_val) => {
+                    index_val < self.balance_of(owner_acc)
+                        && self.tokens_by_owner.get(&(owner_acc, index_val)) == Some(token_id)
+                }
+                None => false,
+            }
+        }
+
+        /// Count `amount_cnt` freshly vesting tokens for `acc`, opening a new window once the
+        /// previous one has fully unlocked.
+        fn add_vesting(&mut self, acc: AccountId, amount_cnt: u32, unlock_ts: u64) -> Result<()> {
+            let now_ts = self.env().block_timestamp();
+            match self.vesting_window.get(&acc) {
+                Some((from_ts, until_ts)) if now_ts < until_ts => {
+                    let vesting_cnt = self.vesting_cnt.get(&acc).unwrap_or(0);
+                    self.vesting_cnt
+                        .insert(&acc, &vesting_cnt.checked_add(amount_cnt).ok_or(Error::Overflow)?);
+                    self.vesting_window.insert(&acc, &(from_ts, max(until_ts, unlock_ts)));
+                }
+                _ => {
+                    self.vesting_cnt.insert(&acc, &amount_cnt);
+                    self.vesting_window.insert(&acc, &(now_ts, unlock_ts));
+                }
+            }
+            Ok(())
+        }
+
+        /// One frozen token fewer for `acc`.
+        fn uncount_frozen(&mut self, acc: AccountId) {
+            let frozen_cnt = self.frozen_cnt.get(&acc).unwrap_or(0);
+            if frozen_cnt <= 1 {
+                self.frozen_cnt.remove(&acc);
+            } else {
+                self.frozen_cnt.insert(&acc, &(frozen_cnt - 1));
+            }
+        }
+
+        /// Drop `token_id`'s vesting lock as it leaves `acc`, uncounting it if it belongs
+        /// to the account's live window (older windows were already reset).
+        fn release_vesting(&mut self, acc: AccountId, token_id: TokenId) {
+            if let Some(unlock_ts) = self.unlock_time.get(&token_id) {
+                self.unlock_time.remove(&token_id);
+                if let Some((from_ts, until_ts)) = self.vesting_window.get(&acc) {
+                    if self.env().block_timestamp() < until_ts && unlock_ts > from_ts {
+                        let vesting_cnt = self.vesting_cnt.get(&acc).unwrap_or(0);
+                        self.vesting_cnt.insert(&acc, &vesting_cnt.saturating_sub(1));
+                    }
+                }
+            }
+        }
+
+        /// Record `acc`'s count for the active epoch before its first change in that epoch.
+        fn checkpoint_holdings(&mut self, acc: AccountId) -> Result<()> {
+            if self.holders_epoch == 0 {
+                return Ok(())
+            }
+            let count_val = self.hold_ckpt_cnt.get(&acc).unwrap_or(0);
+            let last_epoch = match count_val.checked_sub(1) {
+                Some(last_index) => self.hold_ckpt.get(&(acc, last_index)).map(|(epoch, _)| epoch).unwrap_or(0),
+                None => 0,
+            };
+            if last_epoch < self.holders_epoch {
+                self.hold_ckpt.insert(&(acc, count_val), &(self.holders_epoch, self.balance_of(acc)));
+                self.hold_ckpt_cnt.insert(&acc, &count_val.checked_add(1).ok_or(Error::Overflow)?);
+            }
+            Ok(())
+        }
+
+        /// Count at the first checkpoint taken at or after `epoch`, if any
+        /// (binary search over the account's checkpoints).
+        fn holdings_checkpoint_at(&self, acc: AccountId, epoch: u32) -> Option<u32> {
+            let count_val = self.hold_ckpt_cnt.get(&acc).unwrap_or(0);
+            let (mut low_index, mut high_index) = (0u32, count_val);
+            while low_index < high_index {
+                let mid_index = low_index + (high_index - low_index) / 2;
+                let (mid_epoch, _) = self.hold_ckpt.get(&(acc, mid_index))?;
+                if mid_epoch < epoch {
+                    low_index = mid_index + 1;
+                } else {
+                    high_index = mid_index;
+                }
+            }
+            if low_index == count_val {
+                return None
+            }
+            self.hold_ckpt.get(&(acc, low_index)).map(|(_, held_cnt)| held_cnt)
+        }
+
+        fn add_token_to_owner(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
+            let count_val = self.owned_count.get(&to_acc).unwrap_or(0);
+            if let Some(max_tokens) = self.max_tokens_per_owner {
+                if count_val >= max_tokens && to_acc != self.env().account_id() {
+                    return Err(Error::OwnerTokenCap)
+                }
+            }
+            self.checkpoint_holdings(to_acc)?;
+            self.tokens_by_owner.insert(&(to_acc, count_val), &token_id);
+            self.owned_index.insert(&token_id, &count_val);
+            let new_count = count_val.checked_add(1).ok_or(Error::Overflow)?;
+            self.owned_count.insert(&to_acc, &new_count);
+            Ok(())
+        }
+
+        fn remove_token_from_owner(&mut self, from_acc: AccountId, token_id: TokenId) -> Result<()> {
+            let count_val = self.owned_count.get(&from_acc).unwrap_or(0);
+            if count_val == 0 {
+                return Err(Error::TokenMissing)
+            }
+            self.checkpoint_holdings(from_acc)?;
+
+            // index of token to remove
+            let remove_index = self.owned_index.get(&token_id).ok_or(Error::TokenMissing)?;
+
+            // last token info
+            let last_index = count_val - 1;
+            if let Some(last_token_id) = self.tokens_by_owner.get(&(from_acc, last_index)) {
+                // move last token into the removed slot if not the same token
+                if last_index != remove_index {
+                    self.tokens_by_owner.insert(&(from_acc, remove_index), &last_token_id);
+                    self.owned_index.insert(&last_token_id, &remove_index);
+                }
+                // clear last slot
+                self.tokens_by_owner.remove(&(from_acc, last_index));
+            }
+
+            // clear mappings for removed token
+            self.owned_index.remove(&token_id);
+
+            // decrement count
+            self.owned_count.insert(&from_acc, &last_index);
+
+            Ok(())
+        }
+
+        /// Write an operator flag and keep the per-owner operator list in sync.
+        fn set_operator(&mut self, owner_acc: AccountId, operator_acc: AccountId, approved_flag: bool) -> Result<()> {
+            let listed_index = self.operator_index.get(&(owner_acc, operator_acc));
+            let count_val = self.operator_count(owner_acc);
+            match (approved_flag, listed_index) {
+                (true, None) => {
+                    self.operator_by_index.insert(&(owner_acc, count_val), &operator_acc);
+                    self.operator_index.insert(&(owner_acc, operator_acc), &count_val);
+                    let new_count = count_val.checked_add(1).ok_or(Error::Overflow)?;
+                    self.operator_cnt.insert(&owner_acc, &new_count);
+                }
+                (false, Some(remove_index)) => {
+                    let last_index = count_val.checked_sub(1).ok_or(Error::Overflow)?;
+                    if let Some(last_acc) = self.operator_by_index.get(&(owner_acc, last_index)) {
+                        if last_index != remove_index {
+                            self.operator_by_index.insert(&(owner_acc, remove_index), &last_acc);
+                            self.operator_index.insert(&(owner_acc, last_acc), &remove_index);
+                        }
+                        self.operator_by_index.remove(&(owner_acc, last_index));
+                    }
+                    self.operator_index.remove(&(owner_acc, operator_acc));
+                    self.operator_cnt.insert(&owner_acc, &last_index);
+                }
+                _ => {}
+            }
+            // any fresh choice replaces a timed grant; `set_approval_for_all_until` re-adds it
+            self.operator_expiry.remove(&(owner_acc, operator_acc));
+            if approved_flag {
+                self.operator_grant_seq = self.operator_grant_seq.checked_add(1).ok_or(Error::Overflow)?;
+                self.operator_granted_at.insert(&(owner_acc, operator_acc), &self.operator_grant_seq);
+            } else {
+                self.operator_granted_at.remove(&(owner_acc, operator_acc));
+            }
+            self.operator_approval.insert(&(owner_acc, operator_acc), &approved_flag);
+            self.env().emit_event(NFApprovalForAll { owner_acc, operator_acc, approved_flag });
+            Ok(())
+        }
+
+        /// An explicit grant covers `token_id` only if it postdates the snapshot taken when
+        /// the token last left `owner_acc`; the default operator (no explicit record) always does.
+        fn operator_covers(&self, owner_acc: AccountId, operator_acc: AccountId, token_id: TokenId) -> bool {
+            let Some(floor_seq) = self.token_operator_floor.get(&(token_id, owner_acc)) else {
+                return true
+            };
+            if self.operator_approval.get(&(owner_acc, operator_acc)).is_none() {
+                return true
+            }
+            self.operator_granted_at.get(&(owner_acc, operator_acc)).unwrap_or(0) > floor_seq
+        }
+
+        fn operator_expired(&self, owner_acc: AccountId, operator_acc: AccountId) -> bool {
+            match self.operator_expiry.get(&(owner_acc, operator_acc)) {
+                Some(expiry_ts) if expiry_ts != 0 => self.env().block_timestamp() > expiry_ts,
+                _ => false,
+            }
+        }
+
+        fn clear_token_approval(&mut self, token_id: TokenId) {
+            self.token_approval.remove(&token_id);
+            // a listing is a standing sale approval from the previous owner
+            self.listings.remove(&token_id);
+        }
+
+        fn clear_fractionalization(&mut self, token_id: TokenId) {
+            self.fractionalized.remove(&token_id);
+            self.fractional_locker.remove(&token_id);
+        }
+
+        fn spend_phase_alloc(&mut self, phase: u8, amount_cnt: u32) -> Result<()> {
+            let minted_cnt = self.phase_minted_of(phase).checked_add(amount_cnt).ok_or(Error::Overflow)?;
+            if let Some(cap_cnt) = self.phase_cap_of(phase) {
+                if minted_cnt > cap_cnt {
+                    return Err(Error::PhaseCapReached)
+                }
+            }
+            self.phase_minted.insert(&phase, &minted_cnt);
+            Ok(())
+        }
+
+        fn moo_mint_cost(&self, amount_cnt: u32) -> Result<Balance> {
+            let price_val = self.price_in_moo_opt.ok_or(Error::PublicMintClosed)?;
+            price_val.checked_mul(Balance::from(amount_cnt)).ok_or(Error::Overflow)
+        }
+
+        /// Pay (or, with the proceeds queue on, credit) each payee its bps of `total_val`;
+        /// the last payee also takes rounding dust.
+        fn split_revenue(&mut self, minter_acc: AccountId, total_val: Balance) -> Result<()> {
+            let payee_cnt = self.payout_shares.len() as u32;
+            if payee_cnt == 0 || total_val == 0 {
+                return Ok(())
+            }
+            let mut left_val = total_val;
+            for (index_val, (payee_acc, bps_u16)) in self.payout_shares.iter().enumerate() {
+                let share_val = if index_val + 1 == self.payout_shares.len() {
+                    left_val
+                } else {
+                    total_val.checked_mul(Balance::from(*bps_u16)).ok_or(Error::Overflow)? / 10_000
+                };
+                left_val = left_val.saturating_sub(share_val);
+                if share_val == 0 {
+                    continue
+                }
+                if self.queue_proceeds_flag {
+                    let pending_val = self.pending_withdrawals.get(payee_acc).unwrap_or(0);
+                    self.pending_withdrawals
+                        .insert(payee_acc, &pending_val.checked_add(share_val).ok_or(Error::Overflow)?);
+                    self.total_pending = self.total_pending.checked_add(share_val).ok_or(Error::Overflow)?;
+                    self.env().emit_event(ProceedsCredited { payee_acc: *payee_acc, amount_val: share_val });
+                } else {
+                    self.env().transfer(*payee_acc, share_val).map_err(|_| Error::PayoutFailed)?;
+                }
+            }
+            self.env().emit_event(RevenueSplit { minter_acc, total_val, payee_cnt });
+            Ok(())
+        }
+
+        fn withdrawable_balance(&self) -> Balance {
+            self.env()
+                .balance()
+                .saturating_sub(self.min_reserve_balance)
+                .saturating_sub(self.total_pending)
+        }
+
+        fn send_native(&mut self, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            self.env().transfer(to_acc, amount_val).map_err(|_| Error::WithdrawFailed)?;
+            self.env().emit_event(Withdrawn { to_acc, amount_val });
+            Ok(())
+        }
+
+        /// Substrate ECDSA accounts are the blake2-256 of the compressed public key.
+        fn recover_signer(&self, msg_hash: &[u8; 32], signature_val: &[u8; 65]) -> Result<AccountId> {
+            let pub_key = self
+                .env()
+                .ecdsa_recover(signature_val, msg_hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            let acc_bytes = self.env().hash_bytes::<Blake2x256>(&pub_key);
+            Ok(AccountId::from(acc_bytes))
+        }
+
+        /// Read the payer's Moo allowance to this contract and balance so a pull that would
+        /// fail reports why.
+        fn check_moo_funds(&self, moo_acc: AccountId, from_acc: AccountId, amount_val: Balance) -> Result<()> {
+            let moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            let allowance_res = moo_ref
+                .call()
+                .allowance(from_acc, self.env().account_id())
+                .try_invoke();
+            match allowance_res {
+                Ok(Ok(allowance_val)) if allowance_val >= amount_val => {}
+                Ok(Ok(_)) => return Err(Error::MooAllowanceLow),
+                _ => return Err(Error::PaymentFailed),
+            }
+            match moo_ref.call().balance_of(from_acc).try_invoke() {
+                Ok(Ok(balance_val)) if balance_val >= amount_val => Ok(()),
+                Ok(Ok(_)) => Err(Error::MooBalanceLow),
+                _ => Err(Error::PaymentFailed),
+            }
+        }
+
+        /// `pull_moo` into this contract, returning the measured change in its Moo balance.
+        fn pull_moo_in(&self, moo_acc: AccountId, from_acc: AccountId, amount_val: Balance) -> Result<Balance> {
+            if amount_val == 0 {
+                return Ok(0)
+            }
+            let contract_acc = self.env().account_id();
+            let before_val = self.moo_balance_of(moo_acc, contract_acc)?;
+            self.pull_moo(moo_acc, from_acc, contract_acc, amount_val)?;
+            Ok(self.moo_balance_of(moo_acc, contract_acc)?.saturating_sub(before_val))
+        }
+
+        fn moo_balance_of(&self, moo_acc: AccountId, acc: AccountId) -> Result<Balance> {
+            let moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            match moo_ref.call().balance_of(acc).try_invoke() {
+                Ok(Ok(balance_val)) => Ok(balance_val),
+                _ => Err(Error::PaymentFailed),
+            }
+        }
+
+        /// `transfer_from` on the Moo contract with this contract as the spender.
+        fn pull_moo(&self, moo_acc: AccountId, from_acc: AccountId, to_acc: AccountId, amount_val: Balance) -> Result<()> {
+            if amount_val == 0 || from_acc == to_acc {
+                return Ok(())
+            }
+            let mut moo_ref: MooRef = FromAccountId::from_account_id(moo_acc);
+            let call_res = moo_ref
+                .call_mut()
+                .transfer_from(from_acc, to_acc, amount_val)
+                .try_invoke();
+            if !matches!(call_res, Ok(Ok(Ok(())))) {
+                return Err(Error::PaymentFailed)
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn mint_and_transfer_flow() {
+            let mut c = NFMoo::new(Some(10));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.bob));
+            assert_eq!(c.owner_of(1), Some(accounts.bob));
+            assert_eq!(c.balance_of(accounts.bob), 2);
+            let list = c.tokens_of(accounts.bob, 0, 10);
+            assert_eq!(list.len(), 2);
+            assert_eq!(list[0], 0);
+            assert_eq!(list[1], 1);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.charlie));
+            assert_eq!(c.balance_of(accounts.bob), 1);
+            assert_eq!(c.balance_of(accounts.charlie), 1);
+        }
+
+        #[ink::test]
+        fn pause_blocks_mint() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_pause(true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(matches!(c.mint_n(1), Err(Error::Paused)));
+        }
+
+        #[ink::test]
+        fn pauser_can_pause_without_admin_powers() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_pauser(accounts.django, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(c.set_pause(true).is_ok());
+            assert!(c.set_pause(false).is_ok());
+            assert_eq!(c.set_minter(accounts.django, true), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.set_pause(true), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn burner_role_can_burn_any_token() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.grant_role(ROLE_MINTER, accounts.bob).is_ok());
+            assert!(c.grant_role(ROLE_BURNER, accounts.frank).is_ok());
+            assert_eq!(c.grant_role(7, accounts.frank), Err(Error::InvalidRole));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.burn(0), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert!(c.burn(0).is_ok());
+            assert_eq!(c.owner_of(0), None);
+            assert_eq!(c.balance_of(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn shuffled_ids_are_unique_and_capped() {
+            let mut c = NFMoo::new(Some(5));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_shuffle(true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(5).is_ok());
+            let mut list = c.tokens_of(accounts.bob, 0, 10);
+            list.sort();
+            assert_eq!(list, [0, 1, 2, 3, 4]);
+            assert!(c.mint_n(1).is_err());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(c.set_shuffle(false), Err(Error::MintStarted));
+        }
+
+        #[ink::test]
+        fn operator_can_transfer() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.charlie));
+        }
+
+        #[ink::test]
+        fn listing_lifecycle() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.set_market_config(None, accounts.alice, 10_001), Err(Error::InvalidFee));
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.list_for_moo(0, 100).is_ok());
+            assert_eq!(c.listing_of(0), Some((accounts.bob, 100)));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.buy_with_moo(0), Err(Error::MarketUnconfigured));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            assert_eq!(c.listing_of(0), None);
+        }
+
+        #[ink::test]
+        fn reserve_mint_is_owner_only_and_capped() {
+            let mut c = NFMoo::new(Some(3));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.reserve_mint(2).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 2);
+            assert_eq!(c.reserved_count(), 2);
+            assert!(c.reserve_mint(2).is_err());
+            assert_eq!(c.reserved_count(), 2);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.reserve_mint(1), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn try_batch_mint_skips_capped_wallets() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_max_per_wallet(Some(2)).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let flags = c
+                .try_batch_mint(ink::prelude::vec![(accounts.charlie, 2), (accounts.django, 3), (accounts.eve, 1)])
+                .unwrap();
+            assert_eq!(flags, [true, false, true]);
+            assert_eq!(c.balance_of(accounts.charlie), 2);
+            assert_eq!(c.balance_of(accounts.django), 0);
+            assert_eq!(
+                c.batch_mint(ink::prelude::vec![(accounts.charlie, 1)]),
+                Err(Error::WalletCapReached)
+            );
+        }
+
+        #[ink::test]
+        fn operator_list_tracks_approvals() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.set_approval_for_all(accounts.charlie, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.django, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            assert_eq!(c.operator_count(accounts.bob), 3);
+            assert!(c.set_approval_for_all(accounts.charlie, false).is_ok());
+            assert_eq!(c.operators_of(accounts.bob, 0, 10), [accounts.eve, accounts.django]);
+            assert_eq!(c.revoke_all_operators(1), Ok(1));
+            assert_eq!(c.revoke_all_operators(10), Ok(1));
+            assert_eq!(c.operator_count(accounts.bob), 0);
+            assert!(!c.is_approved_for_all(accounts.bob, accounts.eve));
+        }
+
+        #[ink::test]
+        fn withdraw_respects_reserve_floor() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_acc = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            assert!(c.set_min_reserve_balance(100).is_ok());
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract_acc, 1_000);
+            assert_eq!(c.withdraw(accounts.bob, 901), Err(Error::ReserveFloor));
+            assert!(c.withdraw(accounts.bob, 500).is_ok());
+            assert!(c.withdraw_all(accounts.bob).is_ok());
+            assert_eq!(c.withdraw_all(accounts.bob), Err(Error::ReserveFloor));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.withdraw(accounts.bob, 1), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn contract_recipient_policy_toggles() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(!c.block_contract_recipients());
+            assert!(c.set_block_contract_recipients(true).is_ok());
+            assert!(c.block_contract_recipients());
+            // plain accounts still receive mints
+            assert!(c.reserve_mint(1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_block_contract_recipients(false), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn transfer_can_clear_sender_operators() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_clear_operators_on_transfer(true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(c.set_approval_for_all(accounts.django, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            // the receiver's older grants cover the new token
+            assert!(c.can_transfer(accounts.django, 0, None).is_ok());
+            // the seller's other tokens keep their operator
+            assert!(c.is_approved_for_all(accounts.bob, accounts.eve));
+            assert_eq!(c.operator_count(accounts.bob), 1);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.transfer(accounts.django, 0), Err(Error::NotApproved));
+            assert!(c.transfer(accounts.django, 1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.transfer(accounts.django, 0).is_ok());
+        }
+
+        #[ink::test]
+        fn public_mint_splits_revenue() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            let contract_acc = ink::env::test::callee::<E>();
+            assert_eq!(
+                c.set_payout_shares(ink::prelude::vec![(accounts.bob, 6_000), (accounts.charlie, 3_000)]),
+                Err(Error::InvalidShares)
+            );
+            assert!(c
+                .set_payout_shares(ink::prelude::vec![(accounts.bob, 6_000), (accounts.charlie, 4_000)])
+                .is_ok());
+            let bob_before = ink::env::test::get_account_balance::<E>(accounts.bob).unwrap_or(0);
+            let charlie_before = ink::env::test::get_account_balance::<E>(accounts.charlie).unwrap_or(0);
+
+            ink::env::test::set_caller::<E>(accounts.django);
+            assert_eq!(c.mint_public(1), Err(Error::PublicMintClosed));
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.set_price(Some(10)).is_ok());
+
+            ink::env::test::set_caller::<E>(accounts.django);
+            ink::env::test::set_account_balance::<E>(contract_acc, 30);
+            ink::env::test::set_value_transferred::<E>(29);
+            assert_eq!(c.mint_public(3), Err(Error::WrongPayment));
+            ink::env::test::set_value_transferred::<E>(30);
+            assert!(c.mint_public(3).is_ok());
+            assert_eq!(c.balance_of(accounts.django), 3);
+            assert_eq!(ink::env::test::get_account_balance::<E>(accounts.bob).unwrap_or(0), bob_before + 18);
+            assert_eq!(
+                ink::env::test::get_account_balance::<E>(accounts.charlie).unwrap_or(0),
+                charlie_before + 12
+            );
+        }
+
+        #[ink::test]
+        fn frozen_token_cannot_move_burn_or_approve() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert_eq!(c.freeze_token(0, true), Err(Error::TokenMissing));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            assert_eq!(c.freeze_token(0, true), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.freeze_token(0, true).is_ok());
+            assert!(c.is_token_frozen(0));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 0), Err(Error::TokenFrozen));
+            assert_eq!(c.approve(accounts.charlie, 0), Err(Error::TokenFrozen));
+            assert_eq!(c.burn(0), Err(Error::TokenFrozen));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.freeze_token(0, false).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+        }
+
+        #[ink::test]
+        fn all_owned_tokens_returns_full_list() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.all_owned_tokens(accounts.bob), Ok(Vec::new()));
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(3).is_ok());
+            assert_eq!(c.all_owned_tokens(accounts.bob), Ok(ink::prelude::vec![0, 1, 2]));
+        }
+
+        #[ink::test]
+        fn quote_mint_tracks_moo_price() {
+            let mut c = NFMoo::new(None);
+            assert_eq!(c.quote_mint(3), 0);
+            assert!(c.set_price_in_moo(Some(25)).is_ok());
+            assert_eq!(c.price_in_moo(), Some(25));
+            assert_eq!(c.quote_mint(3), 75);
+            assert_eq!(c.mint_paying_moo(3), Err(Error::MarketUnconfigured));
+        }
+
+        #[ink::test]
+        fn fractionalized_token_only_moves_via_vault() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.set_approval_for_all(accounts.charlie, true).is_ok());
+            assert!(c.lock_for_fractionalization(0, accounts.eve).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.eve));
+            assert_eq!(c.fractionalized_vault(0), Some(accounts.eve));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.redeem_from_fractionalization(0), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.set_approval_for_all(accounts.charlie, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.transfer(accounts.charlie, 0), Err(Error::Fractionalized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.redeem_from_fractionalization(0).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.bob));
+            assert_eq!(c.fractionalized_vault(0), None);
+        }
+
+        #[ink::test]
+        fn transfer_range_moves_owned_ids() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(4).is_ok());
+            assert!(c.transfer(accounts.django, 3).is_ok());
+            assert_eq!(c.transfer_range(accounts.charlie, 1, 3), Err(Error::NotOwner));
+            assert_eq!(c.transfer_range(accounts.charlie, 2, 1), Err(Error::AmountZero));
+
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.transfer_range(accounts.charlie, 0, 2).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 1);
+            assert_eq!(c.balance_of(accounts.charlie), 3);
+            assert_eq!(c.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn operator_burn_follows_flag() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.approve(accounts.charlie, 0).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.burn(0), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.set_allow_operator_burn(true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(c.burn(0), Err(Error::NotApproved));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(c.burn(0).is_ok());
+            assert_eq!(c.owner_of(0), None);
+            assert_eq!(c.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn sold_out_tracks_cap_and_reserve() {
+            let mut c = NFMoo::new(Some(3));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.remaining_mintable(), Some(3));
+            assert!(c.reserve_mint(1).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(!c.is_sold_out());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.remaining_mintable(), Some(0));
+            assert!(c.is_sold_out());
+            assert!(!NFMoo::new(None).is_sold_out());
+            assert_eq!(NFMoo::new(None).remaining_mintable(), None);
+        }
+
+        #[ink::test]
+        fn transfer_hook_is_owner_managed() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.transfer_hook(), None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_transfer_hook(Some(accounts.eve)), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.set_transfer_hook(Some(accounts.eve)).is_ok());
+            assert_eq!(c.transfer_hook(), Some(accounts.eve));
+            assert!(c.set_transfer_hook(None).is_ok());
+
+            // with no hook, moves behave as before
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+        }
+
+        #[ink::test]
+        fn token_uri_fills_id_placeholders() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(13).is_ok());
+            assert_eq!(c.token_uri(13), None);
+
+            assert!(c.set_base_uri(String::from("ipfs://cid/")).is_ok());
+            assert_eq!(c.token_uri(12), Some(String::from("ipfs://cid/12")));
+            assert!(c.set_base_uri(String::from("ipfs://cid/{id}.json")).is_ok());
+            assert_eq!(c.token_uri(12), Some(String::from("ipfs://cid/12.json")));
+            assert!(c.set_base_uri(String::from("ar://{id}/{id}")).is_ok());
+            assert_eq!(c.token_uri(7), Some(String::from("ar://7/7")));
+        }
+
+        #[ink::test]
+        fn mint_n_past_cap_reports_max_supply() {
+            let mut c = NFMoo::new(Some(3));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.mint_n(2), Err(Error::MaxSupplyReached));
+            assert_eq!(c.balance_of(accounts.bob), 2);
+            assert!(c.mint_n(1).is_ok());
+            assert_eq!(c.mint_n(1), Err(Error::MaxSupplyReached));
+        }
+
+        #[ink::test]
+        fn permit_approval_checks_deadline_nonce_and_signature() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                c.permit_approval_for_all(accounts.bob, accounts.charlie, true, 0, 0, [0u8; 65]),
+                Err(Error::PermitExpired)
+            );
+            assert_eq!(
+                c.permit_approval_for_all(accounts.bob, accounts.charlie, true, u64::MAX, 1, [0u8; 65]),
+                Err(Error::InvalidNonce)
+            );
+            assert_eq!(
+                c.permit_approval_for_all(accounts.bob, accounts.charlie, true, u64::MAX, 0, [0u8; 65]),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(c.nft_nonce(accounts.bob), 0);
+            assert!(!c.is_approved_for_all(accounts.bob, accounts.charlie));
+        }
+
+        #[ink::test]
+        fn mint_paying_moo_checks_config_first() {
+            let mut c = NFMoo::new(Some(2));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.mint_paying_moo(1), Err(Error::MarketUnconfigured));
+            assert!(c.set_market_config(Some(accounts.frank), accounts.eve, 0).is_ok());
+            assert_eq!(c.mint_paying_moo(1), Err(Error::PublicMintClosed));
+            assert!(c.set_price_in_moo(Some(5)).is_ok());
+            assert_eq!(c.mint_paying_moo(3), Err(Error::MaxSupplyReached));
+            assert_eq!(c.mint_paying_moo(0), Err(Error::AmountZero));
+        }
+
+        #[ink::test]
+        fn token_detail_collects_fields() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.set_base_uri(String::from("ipfs://cid/{id}.json")).is_ok());
+            assert!(c.approve(accounts.bob, 0).is_ok());
+            assert!(c.set_token_tier(0, Some(2)).is_ok());
+            assert!(c.freeze_token(0, true).is_ok());
+            assert_eq!(
+                c.token_detail(0),
+                TokenDetail {
+                    owner: Some(accounts.alice),
+                    approved: Some(accounts.bob),
+                    uri: Some(String::from("ipfs://cid/0.json")),
+                    locked: true,
+                    tier: Some(2),
+                }
+            );
+            assert_eq!(
+                c.token_detail(9),
+                TokenDetail { owner: None, approved: None, uri: None, locked: false, tier: None }
+            );
+        }
+
+        #[ink::test]
+        fn token_royalty_overrides_default() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.royalty_info(0, 1_000), None);
+            assert!(c.set_default_royalty(Some((accounts.bob, 500))).is_ok());
+            assert_eq!(c.set_token_royalty(1, accounts.charlie, 10_001), Err(Error::InvalidFee));
+            assert!(c.set_token_royalty(1, accounts.charlie, 1_000).is_ok());
+            assert_eq!(c.royalty_info(0, 1_000), Some((accounts.bob, 50)));
+            assert_eq!(c.royalty_info(1, 1_000), Some((accounts.charlie, 100)));
+            assert!(c.burn(1).is_ok());
+            assert_eq!(c.royalty_info(1, 1_000), Some((accounts.bob, 50)));
+        }
+
+        #[ink::test]
+        fn approved_operators_lists_live_flags() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_approval_for_all(accounts.bob, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.charlie, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.django, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.bob, false).is_ok());
+            let list_vec = c.approved_operators(accounts.alice, 0, 10);
+            assert_eq!(list_vec.len(), 2);
+            assert!(list_vec.contains(&(accounts.charlie, true)));
+            assert!(list_vec.contains(&(accounts.django, true)));
+            assert_eq!(c.approved_operators(accounts.alice, 1, 10).len(), 1);
+        }
+
+        #[ink::test]
+        fn locked_mint_blocks_transfer_until_unlock() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            let unlock_ts = ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 1;
+            assert!(c.mint_locked(accounts.bob, 2, unlock_ts).is_ok());
+            assert_eq!(c.unlock_time_of(1), unlock_ts);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 1), Err(Error::TokenLocked));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert!(c.transfer(accounts.charlie, 1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(c.mint_locked(accounts.django, 1, 0), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn tokens_of_many_groups_by_owner() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.batch_mint(ink::prelude::vec![(accounts.bob, 3), (accounts.charlie, 1)]).is_ok());
+            let list_vec = c.tokens_of_many(ink::prelude::vec![accounts.bob, accounts.charlie, accounts.django], 2);
+            assert_eq!(
+                list_vec,
+                ink::prelude::vec![
+                    (accounts.bob, ink::prelude::vec![0, 1]),
+                    (accounts.charlie, ink::prelude::vec![3]),
+                    (accounts.django, Vec::new()),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn mint_with_id_coexists_with_sequential_ids() {
+            let mut c = NFMoo::new(Some(4));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_with_id(accounts.bob, 1).is_ok());
+            assert_eq!(c.mint_with_id(accounts.charlie, 1), Err(Error::TokenExists));
+            assert!(c.mint_with_id(accounts.bob, 9_000).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.alice));
+            assert_eq!(c.owner_of(1), Some(accounts.bob));
+            assert_eq!(c.owner_of(2), Some(accounts.alice));
+            assert_eq!(c.tokens_of(accounts.bob, 0, 10), ink::prelude::vec![1, 9_000]);
+            assert_eq!(c.mint_with_id(accounts.bob, 5), Err(Error::MaxSupplyReached));
+        }
+
+        #[ink::test]
+        fn mint_can_emit_uri_event() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_base_uri(String::from("ipfs://cid/{id}.json")).is_ok());
+            assert!(c.set_emit_uri_on_mint(true).is_ok());
+            assert!(c.emit_uri_on_mint());
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c.mint_n(2).is_ok());
+            // one merged event per token
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 2);
+            assert_eq!(c.token_uri(1), Some(String::from("ipfs://cid/1.json")));
+        }
+
+        #[ink::test]
+        fn claim_mint_spends_allowance() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.grant_mint_allowance(accounts.bob, 3).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.claim_mint(4), Err(Error::NoMintAllowance));
+            assert!(c.claim_mint(2).is_ok());
+            assert_eq!(c.mint_allowance_of(accounts.bob), 1);
+            assert_eq!(c.balance_of(accounts.bob), 2);
+            assert!(c.claim_mint(1).is_ok());
+            assert_eq!(c.claim_mint(1), Err(Error::NoMintAllowance));
+            assert_eq!(c.grant_mint_allowance(accounts.bob, 5), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn operator_burn_matches_transfer_authority() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_allow_operator_burn(true).is_ok());
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(3).is_ok());
+            assert!(c.set_approval_for_all(accounts.charlie, true).is_ok());
+            assert!(c.approve(accounts.django, 2).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(c.burn(0).is_ok());
+            assert!(c.burn(1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(c.burn(3), Err(Error::NotApproved));
+            assert!(c.burn(2).is_ok());
+            assert_eq!(c.tokens_of(accounts.bob, 0, 10), ink::prelude::vec![3]);
+        }
+
+        #[ink::test]
+        fn new_with_minter_can_mint_immediately() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut c = NFMoo::new_with_minter(Some(5), true);
+            assert!(c.has_role(ROLE_MINTER, accounts.alice));
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 2);
+
+            let mut d = NFMoo::new_with_minter(Some(5), false);
+            assert!(!d.has_role(ROLE_MINTER, accounts.alice));
+            assert_eq!(d.mint_n(1), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn supports_interface_reports_implemented_ids() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let transfer_only = interface_id(&[ink::selector_bytes!("PSP34::transfer")]);
+            assert_eq!(transfer_only, ink::selector_bytes!("PSP34::transfer"));
+            assert!(c.supports_interface(INTERFACE_DISCOVERY));
+            assert!(c.supports_interface(INTERFACE_PSP34));
+            assert!(c.supports_interface(INTERFACE_PSP34_ENUMERABLE));
+            assert!(!c.supports_interface(INTERFACE_PSP34_METADATA));
+            assert!(!c.supports_interface(INTERFACE_ROYALTY));
+            assert!(c.set_base_uri(String::from("ipfs://cid/")).is_ok());
+            assert!(c.set_default_royalty(Some((accounts.bob, 500))).is_ok());
+            assert!(c.supports_interface(INTERFACE_PSP34_METADATA));
+            assert!(c.supports_interface(INTERFACE_ROYALTY));
+            assert!(!c.supports_interface([0xff; 4]));
+            assert!(!c.supports_interface([0u8; 4]));
+        }
+
+        #[ink::test]
+        fn repair_enumeration_restores_lost_token() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.repair_enumeration(1, accounts.alice), Err(Error::EnumerationIntact));
+            // simulate a migration that dropped token 1 from the owner's list
+            c.tokens_by_owner.remove(&(accounts.alice, 1));
+            c.owned_index.remove(&1);
+            c.owned_count.insert(&accounts.alice, &1);
+            assert_eq!(c.tokens_of(accounts.alice, 0, 10), ink::prelude::vec![0]);
+            assert_eq!(c.repair_enumeration(1, accounts.bob), Err(Error::TokenMissing));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.repair_enumeration(1, accounts.alice), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.repair_enumeration(1, accounts.alice).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 2);
+            assert_eq!(c.tokens_of(accounts.alice, 0, 10), ink::prelude::vec![0, 1]);
+        }
+
+        #[ink::test]
+        fn can_mint_matches_mint_n() {
+            let mut c = NFMoo::new(Some(3));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.can_mint(accounts.bob, 1), Err(Error::Unauthorized));
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert_eq!(c.can_mint(accounts.bob, 0), Err(Error::AmountZero));
+            assert_eq!(c.can_mint(accounts.bob, 4), Err(Error::MaxSupplyReached));
+            assert!(c.set_max_per_wallet(Some(2)).is_ok());
+            assert_eq!(c.can_mint(accounts.bob, 3), Err(Error::WalletCapReached));
+            assert!(c.can_mint(accounts.bob, 2).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.can_mint(accounts.bob, 1), Err(Error::WalletCapReached));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(c.can_mint(accounts.bob, 1), Err(Error::Paused));
+        }
+
+        #[ink::test]
+        fn public_price_rises_per_mint() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_price(Some(100)).is_ok());
+            assert!(c.set_price_step(10, true).is_ok());
+            // 100 + 110 + 120
+            assert_eq!(c.public_mint_cost(3), Ok(330));
+            ink::env::test::set_caller::<E>(accounts.bob);
+            ink::env::test::set_account_balance::<E>(accounts.bob, 1_000);
+            ink::env::test::set_value_transferred::<E>(300);
+            assert_eq!(c.mint_public(3), Err(Error::WrongPayment));
+            ink::env::test::set_value_transferred::<E>(330);
+            assert!(c.mint_public(3).is_ok());
+            assert_eq!(c.current_price(), Some(130));
+            assert_eq!(c.public_mint_cost(1), Ok(130));
+        }
+
+        #[ink::test]
+        fn transfer_lock_passes_whitelisted_only() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            assert!(c.set_transfers_locked(true).is_ok());
+            assert!(c.transfers_locked());
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::Paused));
+            assert!(c.set_transfer_whitelist(accounts.eve, true).is_ok());
+            assert!(c.transfer(accounts.eve, 0).is_ok());
+            assert!(c.set_transfer_whitelist(accounts.alice, true).is_ok());
+            assert!(c.transfer(accounts.bob, 1).is_ok());
+            assert!(c.set_transfer_whitelist(accounts.alice, false).is_ok());
+            assert_eq!(c.transfer(accounts.bob, 2), Err(Error::Paused));
+            assert!(c.set_transfers_locked(false).is_ok());
+            assert!(c.transfer(accounts.bob, 2).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_transfers_locked(true), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn approval_for_all_many_keeps_input_order() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_approval_for_all(accounts.bob, true).is_ok());
+            assert!(c.set_approval_for_all(accounts.django, true).is_ok());
+            assert_eq!(
+                c.is_approved_for_all_many(accounts.alice, ink::prelude::vec![accounts.django, accounts.charlie, accounts.bob]),
+                ink::prelude::vec![true, false, true]
+            );
+            let long_vec = ink::prelude::vec![accounts.bob; MAX_OPERATOR_QUERY + 5];
+            assert_eq!(c.is_approved_for_all_many(accounts.alice, long_vec).len(), MAX_OPERATOR_QUERY);
+        }
+
+        #[ink::test]
+        fn claim_airdrop_mints_allocation_once() {
+            let mut c = NFMoo::new(Some(4));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_airdrop_allocation(accounts.bob, 3).is_ok());
+            assert!(c.set_airdrop_allocation(accounts.charlie, 2).is_ok());
+            assert_eq!(c.airdrop_allocation_of(accounts.bob), 3);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.claim_airdrop().is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 3);
+            assert_eq!(c.airdrop_allocation_of(accounts.bob), 0);
+            assert_eq!(c.claim_airdrop(), Err(Error::NoAllocation));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.claim_airdrop(), Err(Error::MaxSupplyReached));
+            assert_eq!(c.airdrop_allocation_of(accounts.charlie), 2);
+            assert_eq!(c.set_airdrop_allocation(accounts.charlie, 1), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn collection_stats_bundles_counters() {
+            let mut c = NFMoo::new(Some(10));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            assert!(c.burn(1).is_ok());
+            assert!(c.set_price(Some(5)).is_ok());
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(
+                c.collection_stats(),
+                CollectionStats {
+                    total_supply: 2,
+                    max_supply: Some(10),
+                    minted_cnt: 3,
+                    burned_cnt: 1,
+                    next_id: 3,
+                    sale_phase: SALE_PUBLIC,
+                    paused: true,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn opt_in_gates_transfers_then_mints() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_require_opt_in(true, false).is_ok());
+            // mints exempt by default
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::NotOptedIn));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.opt_in().is_ok());
+            assert!(c.is_opted_in(accounts.bob));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.set_require_opt_in(true, true).is_ok());
+            assert_eq!(c.mint_n(1), Err(Error::NotOptedIn));
+            assert!(c.opt_in().is_ok());
+            assert!(c.mint_n(1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.opt_out().is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(c.transfer(accounts.bob, 1), Err(Error::NotOptedIn));
+        }
+
+        #[ink::test]
+        fn locked_balance_counts_frozen_and_vesting() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            let unlock_ts = ink::env::block_timestamp::<E>() + 1_000;
+            assert!(c.mint_locked(accounts.alice, 2, unlock_ts).is_ok());
+            assert_eq!(c.balance_of(accounts.alice), 4);
+            assert_eq!(c.locked_balance_of(accounts.alice), 2);
+            assert!(c.freeze_token(0, true).is_ok());
+            assert!(c.freeze_token(0, true).is_ok());
+            assert_eq!(c.locked_balance_of(accounts.alice), 3);
+            assert_eq!(c.transferable_balance_of(accounts.alice), 1);
+            assert!(c.burn(2).is_ok());
+            assert_eq!(c.locked_balance_of(accounts.alice), 2);
+            ink::env::test::set_block_timestamp::<E>(unlock_ts);
+            assert_eq!(c.locked_balance_of(accounts.alice), 1);
+            assert!(c.transfer(accounts.bob, 3).is_ok());
+            assert_eq!(c.unlock_time_of(3), 0);
+            assert!(c.freeze_token(0, false).is_ok());
+            assert_eq!(c.locked_balance_of(accounts.alice), 0);
+            assert_eq!(c.transferable_balance_of(accounts.alice), 2);
+        }
+
+        #[ink::test]
+        fn minter_fee_charged_on_mint_n() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert!(c.set_minter_fee(5).is_ok());
+            assert_eq!(c.minter_fee(), 5);
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.set_minter_fee(0), Err(Error::NotOwner));
+            ink::env::test::set_value_transferred::<E>(9);
+            assert_eq!(c.mint_n(2), Err(Error::WrongPayment));
+            ink::env::test::set_value_transferred::<E>(10);
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 2);
+        }
+
+        #[ink::test]
+        fn withdraw_royalties_needs_balance_and_market() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.royalty_owed_of(accounts.bob), 0);
+            assert_eq!(c.withdraw_royalties(), Err(Error::MarketUnconfigured));
+            assert!(c.set_market_config(Some(accounts.frank), accounts.eve, 250).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.withdraw_royalties(), Err(Error::AmountZero));
+            c.royalty_owed.insert(&accounts.bob, &40);
+            assert_eq!(c.royalty_owed_of(accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn default_operator_approved_until_opt_out() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert!(c.set_default_operator(Some(accounts.eve)).is_ok());
+            assert_eq!(c.default_operator(), Some(accounts.eve));
+            assert!(c.is_approved_for_all(accounts.bob, accounts.eve));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.mint_n(2).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.set_approval_for_all(accounts.eve, false).is_ok());
+            assert!(!c.is_approved_for_all(accounts.bob, accounts.eve));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.transfer(accounts.charlie, 1), Err(Error::NotApproved));
+            // revoke_all_operators opts out of the default too
+            assert!(c.is_approved_for_all(accounts.charlie, accounts.eve));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(c.revoke_all_operators(5), Ok(1));
+            assert!(!c.is_approved_for_all(accounts.charlie, accounts.eve));
+        }
+
+        #[ink::test]
+        fn batch_transfer_mixed_routes_each_token() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            let before_cnt = ink::env::test::recorded_events().count();
+            assert!(c
+                .batch_transfer_mixed(ink::prelude::vec![(accounts.bob, 0), (accounts.charlie, 2)])
+                .is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), before_cnt + 2);
+            assert_eq!(c.owner_of(0), Some(accounts.bob));
+            assert_eq!(c.owner_of(2), Some(accounts.charlie));
+            assert_eq!(
+                c.batch_transfer_mixed(ink::prelude::vec![(accounts.django, 1), (accounts.django, 0)]),
+                Err(Error::NotApproved)
+            );
+            let too_many = ink::prelude::vec![(accounts.bob, 1); MAX_PER_CALL as usize + 1];
+            assert_eq!(c.batch_transfer_mixed(too_many), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn holdings_at_reads_snapshot_counts() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            assert_eq!(c.holdings_at(accounts.alice, 1), Err(Error::InvalidSnapshot));
+            assert_eq!(c.snapshot_holders(), Ok(1));
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.transfer(accounts.bob, 1).is_ok());
+            assert_eq!(c.snapshot_holders(), Ok(2));
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.holdings_at(accounts.alice, 1), Ok(3));
+            assert_eq!(c.holdings_at(accounts.bob, 1), Ok(0));
+            assert_eq!(c.holdings_at(accounts.alice, 2), Ok(1));
+            assert_eq!(c.holdings_at(accounts.bob, 2), Ok(2));
+            assert_eq!(c.balance_of(accounts.alice), 3);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.snapshot_holders(), Err(Error::NotOwner));
         }
 
-        #[ink(message)]
-        pub fn get_approved(&self, token_id: TokenId) -> Option<AccountId> {
-            self.token_approval.get(&token_id)
+        #[ink::test]
+        fn redeem_requires_tier_reward() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.redeem(0), Err(Error::MarketUnconfigured));
+            assert!(c.set_market_config(Some(accounts.frank), accounts.eve, 0).is_ok());
+            assert_eq!(c.redeem(0), Err(Error::NoReward));
+            assert!(c.set_token_tier(0, Some(2)).is_ok());
+            assert_eq!(c.tier_of(0), Some(2));
+            assert_eq!(c.redeem(0), Err(Error::NoReward));
+            assert!(c.set_tier_reward(2, Some(500)).is_ok());
+            assert_eq!(c.tier_reward_of(2), Some(500));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.redeem(0), Err(Error::NotOwner));
+            assert_eq!(c.set_tier_reward(2, None), Err(Error::NotOwner));
         }
 
-        #[ink(message)]
-        pub fn is_approved_for_all(&self, owner_acc: AccountId, operator_acc: AccountId) -> bool {
-            self.operator_approval.get(&(owner_acc, operator_acc)).unwrap_or(false)
+        #[ink::test]
+        fn queued_proceeds_are_pulled_by_payees() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            let contract_acc = ink::env::test::callee::<E>();
+            assert!(c.set_min_reserve_balance(0).is_ok());
+            assert!(c
+                .set_payout_shares(ink::prelude::vec![(accounts.bob, 6_000), (accounts.charlie, 4_000)])
+                .is_ok());
+            assert!(c.set_queue_proceeds(true).is_ok());
+            assert!(c.set_price(Some(10)).is_ok());
+            ink::env::test::set_caller::<E>(accounts.django);
+            ink::env::test::set_account_balance::<E>(contract_acc, 30);
+            ink::env::test::set_value_transferred::<E>(30);
+            assert!(c.mint_public(3).is_ok());
+            assert_eq!(c.pending_withdrawal_of(accounts.bob), 18);
+            assert_eq!(c.pending_withdrawal_of(accounts.charlie), 12);
+            ink::env::test::set_caller::<E>(accounts.alice);
+            // queued proceeds are not the owner's to sweep
+            assert_eq!(c.withdraw_all(accounts.alice), Err(Error::ReserveFloor));
+            let bob_before = ink::env::test::get_account_balance::<E>(accounts.bob).unwrap_or(0);
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.withdraw_proceeds().is_ok());
+            assert_eq!(ink::env::test::get_account_balance::<E>(accounts.bob).unwrap_or(0), bob_before + 18);
+            assert_eq!(c.pending_withdrawal_of(accounts.bob), 0);
+            assert_eq!(c.withdraw_proceeds(), Err(Error::AmountZero));
         }
 
-        // -------- queries --------
+        #[ink::test]
+        fn hold_period_blocks_quick_flips() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_min_hold_blocks(2).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            let minted_at = ink::env::block_number::<E>();
+            assert_eq!(c.acquired_block_of(0), Some(minted_at));
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::HoldPeriod));
+            ink::env::test::advance_block::<E>();
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::HoldPeriod));
+            ink::env::test::advance_block::<E>();
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert_eq!(c.acquired_block_of(0), Some(minted_at + 2));
+            // the clock restarts for the new holder; zero lifts the rule
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 0), Err(Error::HoldPeriod));
+            assert_eq!(c.set_min_hold_blocks(0), Err(Error::NotOwner));
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.set_min_hold_blocks(0).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+        }
 
-        /// Who owns this token?
-        #[ink(message)]
-        pub fn owner_of(&self, token_id: TokenId) -> Option<AccountId> {
-            self.owner_by_id.get(&token_id)
+        #[ink::test]
+        fn wrap_and_unwrap_check_config_and_wrapper() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert_eq!(c.wrap(0), Err(Error::MarketUnconfigured));
+            assert!(c.set_market_config(Some(accounts.frank), accounts.eve, 0).is_ok());
+            assert_eq!(c.wrap(0), Err(Error::MarketUnconfigured));
+            assert!(c.set_wrap_unit(1_000).is_ok());
+            assert_eq!(c.wrap_unit(), 1_000);
+            assert_eq!(c.wrapped_by(0), None);
+            assert_eq!(c.unwrap(0), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.wrap(0), Err(Error::NotOwner));
+            assert_eq!(c.set_wrap_unit(1), Err(Error::NotOwner));
         }
 
-        /// How many tokens does this account own?
-        #[ink(message)]
-        pub fn balance_of(&self, owner_acc: AccountId) -> u32 {
-            self.owned_count.get(&owner_acc).unwrap_or(0)
+        #[ink::test]
+        fn estimate_batch_units_scales_with_count() {
+            let mut c = NFMoo::new(None);
+            assert_eq!(c.estimate_batch_units(0), 0);
+            assert_eq!(c.estimate_batch_units(1), TRANSFER_WRITES_PER_ITEM + BATCH_WRITES);
+            assert_eq!(c.estimate_batch_units(10), 10 * TRANSFER_WRITES_PER_ITEM + BATCH_WRITES);
+            assert!(c.snapshot_holders().is_ok());
+            assert_eq!(
+                c.estimate_batch_units(1),
+                TRANSFER_WRITES_PER_ITEM + 2 * CHECKPOINT_WRITES + BATCH_WRITES
+            );
         }
 
-        /// Paginated list of token ids owned by `owner_acc`.
-        #[ink(message)]
-        pub fn tokens_of(&self, owner_acc: AccountId, start_index: u32, limit_cnt: u32) -> Vec<TokenId> {
-            let count_val = self.balance_of(owner_acc);
-            if start_index >= count_val || limit_cnt == 0 {
-                return Vec::new()
-            }
-            let end_index = min(count_val, start_index.saturating_add(limit_cnt));
-            let mut list_vec: Vec<TokenId> = Vec::new();
-            let mut index_val = start_index;
-            while index_val < end_index {
-                if let Some(token_id) = self.tokens_by_owner.get(&(owner_acc, index_val)) {
-                    list_vec.push(token_id);
-                }
-                index_val += 1;
-            }
-            list_vec
+        #[ink::test]
+        fn mint_with_token_requires_accepted_token() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(c.mint_with_token(accounts.frank, 1), Err(Error::TokenNotAccepted));
+            assert!(c.set_accepted_token(accounts.frank, Some(5)).is_ok());
+            assert!(c.set_accepted_token(accounts.eve, Some(7)).is_ok());
+            assert!(c.set_accepted_token(accounts.frank, Some(6)).is_ok());
+            assert_eq!(c.accepted_tokens(), ink::prelude::vec![(accounts.frank, 6), (accounts.eve, 7)]);
+            assert_eq!(c.mint_with_token(accounts.frank, 0), Err(Error::AmountZero));
+            assert!(c.set_accepted_token(accounts.frank, None).is_ok());
+            assert_eq!(c.accepted_tokens(), ink::prelude::vec![(accounts.eve, 7)]);
+            assert_eq!(c.mint_with_token(accounts.frank, 1), Err(Error::TokenNotAccepted));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.set_accepted_token(accounts.bob, Some(1)), Err(Error::NotOwner));
         }
 
-        // -------- internals: owner sets management --------
+        #[ink::test]
+        fn transfer_if_owner_rejects_moved_token() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(c.transfer_if_owner(accounts.alice, accounts.charlie, 0), Err(Error::NotOwner));
+            assert_eq!(c.transfer_if_owner(accounts.bob, accounts.charlie, 0), Err(Error::NotApproved));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer_if_owner(accounts.bob, accounts.charlie, 0).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.charlie));
+            assert_eq!(c.transfer_if_owner(accounts.bob, accounts.charlie, 7), Err(Error::TokenMissing));
+        }
 
-        fn add_token_to_owner(&mut self, to_acc: AccountId, token_id: TokenId) -> Result<()> {
-            let count_val = self.owned_count.get(&to_acc).unwrap_or(0);
-            self.tokens_by_owner.insert(&(to_acc, count_val), &token_id);
-            self.owned_index.insert(&token_id, &count_val);
-            let new_count = count_val.checked_add(1).ok_or(Error::Overflow)?;
-            self.owned_count.insert(&to_acc, &new_count);
-            Ok(())
+        #[ink::test]
+        fn phase_cap_bounds_public_mints() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(Some(10));
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_price(Some(10)).is_ok());
+            assert!(c.set_phase_cap(SALE_PUBLIC, Some(3)).is_ok());
+            assert_eq!(c.phase_cap_of(SALE_PUBLIC), Some(3));
+            ink::env::test::set_caller::<E>(accounts.bob);
+            ink::env::test::set_value_transferred::<E>(20);
+            assert!(c.mint_public(2).is_ok());
+            assert_eq!(c.mint_public(2), Err(Error::PhaseCapReached));
+            assert_eq!(c.phase_minted_of(SALE_PUBLIC), 2);
+            ink::env::test::set_value_transferred::<E>(10);
+            assert!(c.mint_public(1).is_ok());
+            assert_eq!(c.phase_minted_of(SALE_PUBLIC), 3);
+            assert_eq!(c.phase_minted_of(SALE_MOO), 0);
+            assert_eq!(c.set_phase_cap(SALE_PUBLIC, None), Err(Error::NotOwner));
         }
 
-        fn remove_token_from_owner(&mut self, from_acc: AccountId, token_id: TokenId) -> Result<()> {
-            let count_val = self.owned_count.get(&from_acc).unwrap_or(0);
-            if count_val == 0 {
-                return Err(Error::TokenMissing)
-            }
+        #[ink::test]
+        fn owner_token_cap_blocks_mint_and_transfer_in() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            assert!(c.set_max_tokens_per_owner(Some(2)).is_ok());
+            assert_eq!(c.max_tokens_per_owner(), Some(2));
+            assert_eq!(c.mint_n(1), Err(Error::OwnerTokenCap));
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.transfer(accounts.bob, 1).is_ok());
+            assert_eq!(c.transfer(accounts.bob, 2), Err(Error::OwnerTokenCap));
+            // over-cap holders can still shed tokens
+            assert!(c.burn(2).is_ok());
+            assert_eq!(c.balance_of(accounts.bob), 2);
+        }
 
-            // index of token to remove
-            let remove_index = self.owned_index.get(&token_id).ok_or(Error::TokenMissing)?;
+        #[ink::test]
+        fn reveal_lock_holds_transfers_until_reveal() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.set_lock_until_reveal(true).is_ok());
+            assert!(c.transfers_reveal_locked());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::NotRevealed));
+            assert!(c.burn(1).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.reveal(), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.reveal().is_ok());
+            assert!(c.is_revealed());
+            assert!(!c.transfers_reveal_locked());
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+        }
 
-            // last token info
-            let last_index = count_val - 1;
-            if let Some(last_token_id) = self.tokens_by_owner.get(&(from_acc, last_index)) {
-                // move last token into the removed slot if not the same token
-                if last_index != remove_index {
-                    self.tokens_by_owner.insert(&(from_acc, remove_index), &last_token_id);
-                    self.owned_index.insert(&last_token_id, &remove_index);
-                }
-                // clear last slot
-                self.tokens_by_owner.remove(&(from_acc, last_index));
-            }
+        #[ink::test]
+        fn resale_cooldown_can_cover_direct_transfers() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.set_resale_cooldown(2, false).is_ok());
+            assert_eq!(c.resale_cooldown(), (2, false));
+            // as recorded by a marketplace sale (which needs a deployed Moo)
+            c.last_sale_block.insert(&0, &ink::env::block_number::<E>());
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.set_resale_cooldown(2, true).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.transfer(accounts.charlie, 0), Err(Error::ResaleCooldown));
+            ink::env::test::advance_block::<E>();
+            ink::env::test::advance_block::<E>();
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            assert_eq!(c.set_resale_cooldown(0, false), Err(Error::NotOwner));
+        }
 
-            // clear mappings for removed token
-            self.owned_index.remove(&token_id);
+        #[ink::test]
+        fn pending_transfer_waits_for_recipient() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_acc = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.transfer_pending(accounts.bob, 0).is_ok());
+            assert_eq!(c.owner_of(0), Some(contract_acc));
+            assert_eq!(c.pending_transfer_of(0), Some((accounts.alice, accounts.bob)));
+            assert_eq!(c.accept_transfer(0), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.cancel_transfer(0), Err(Error::NotOwner));
+            assert!(c.accept_transfer(0).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.bob));
+            assert_eq!(c.pending_transfer_of(0), None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.transfer_pending(accounts.charlie, 1).is_ok());
+            assert!(c.cancel_transfer(1).is_ok());
+            assert_eq!(c.owner_of(1), Some(accounts.alice));
+        }
 
-            // decrement count
-            self.owned_count.insert(&from_acc, &last_index);
+        #[ink::test]
+        fn staged_reveal_uncovers_low_ids_first() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(3).is_ok());
+            assert!(c.set_base_uri(String::from("ipfs://cid/{id}.json")).is_ok());
+            assert!(c.set_placeholder_uri(String::from("ipfs://hidden.json")).is_ok());
+            assert_eq!(c.token_uri(0), Some(String::from("ipfs://hidden.json")));
+            assert!(c.reveal_up_to(1).is_ok());
+            assert_eq!(c.token_uri(1), Some(String::from("ipfs://cid/1.json")));
+            assert_eq!(c.token_uri(2), Some(String::from("ipfs://hidden.json")));
+            assert_eq!(c.reveal_up_to(0), Err(Error::RevealBackwards));
+            assert_eq!(c.reveal_up_to(1), Err(Error::RevealBackwards));
+            assert!(c.reveal().is_ok());
+            assert_eq!(c.token_uri(2), Some(String::from("ipfs://cid/2.json")));
+        }
 
-            Ok(())
+        #[ink::test]
+        fn minted_count_survives_transfer_and_burn() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.set_price(Some(10)).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            ink::env::test::set_value_transferred::<E>(10);
+            assert!(c.mint_public(1).is_ok());
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.burn(1).is_ok());
+            assert_eq!(c.minted_count_of(accounts.alice), 2);
+            assert_eq!(c.minted_count_of(accounts.bob), 1);
+            assert_eq!(c.balance_of(accounts.alice), 0);
         }
 
-        fn clear_token_approval(&mut self, token_id: TokenId) {
-            self.token_approval.remove(&token_id);
+        #[ink::test]
+        fn can_transfer_reports_transfer_errors() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert_eq!(c.can_transfer(accounts.alice, 0, None), Ok(()));
+            assert_eq!(c.can_transfer(accounts.bob, 0, None), Err(Error::NotApproved));
+            assert_eq!(c.can_transfer(accounts.alice, 5, None), Err(Error::TokenMissing));
+            assert!(c.set_lock_until_reveal(true).is_ok());
+            assert_eq!(c.can_transfer(accounts.alice, 0, None), Err(Error::NotRevealed));
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::NotRevealed));
+            assert!(c.set_lock_until_reveal(false).is_ok());
+            assert_eq!(c.can_transfer(accounts.alice, 0, Some(accounts.alice)), Err(Error::SameAccount));
+            assert!(c.set_require_opt_in(true, false).is_ok());
+            assert_eq!(c.can_transfer(accounts.alice, 0, Some(accounts.bob)), Err(Error::NotOptedIn));
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::NotOptedIn));
+            assert_eq!(c.can_transfer(accounts.alice, 0, None), Ok(()));
+            assert!(c.set_pause(true).is_ok());
+            assert_eq!(c.can_transfer(accounts.alice, 0, None), Err(Error::Paused));
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        #[ink::test]
+        fn transfer_count_tracks_every_move() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.transfer_count_of(0), 0);
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert!(c.batch_transfer_mixed(ink::prelude::vec![(accounts.charlie, 1)]).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.transfer(accounts.charlie, 0).is_ok());
+            assert_eq!(c.transfer_count_of(0), 2);
+            assert_eq!(c.transfer_count_of(1), 1);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(c.burn(0).is_ok());
+            assert_eq!(c.transfer_count_of(0), 0);
+        }
 
         #[ink::test]
-        fn mint_and_transfer_flow() {
-            let mut c = NFMoo::new(Some(10));
+        fn mintable_remaining_matches_cap_enforcement() {
+            let mut c = NFMoo::new(Some(3));
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert_eq!(c.mintable_remaining(), Some(3));
+            assert!(c.mint_n(3).is_ok());
+            assert_eq!(c.mintable_remaining(), Some(0));
+            assert_eq!(c.mint_n(1), Err(Error::MaxSupplyReached));
+            // a burn frees one slot; the replacement gets a fresh id
+            assert!(c.burn(0).is_ok());
+            assert_eq!(c.mintable_remaining(), Some(1));
+            assert_eq!(c.mintable_remaining_lifetime(), Some(0));
+            assert!(c.mint_n(1).is_ok());
+            assert_eq!(c.owner_of(3), Some(accounts.alice));
+            assert_eq!(c.collection_stats().minted_cnt, 4);
+            assert_eq!(c.mint_n(1), Err(Error::MaxSupplyReached));
+            assert_eq!(c.mintable_remaining_lifetime(), Some(0));
+            assert_eq!(NFMoo::new(None).mintable_remaining(), None);
+            assert_eq!(NFMoo::new(None).mintable_remaining_lifetime(), None);
+        }
+
+        #[ink::test]
+        fn timed_operator_approval_lapses() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            let now_ts = ink::env::block_timestamp::<E>();
+            assert_eq!(c.set_approval_for_all_until(accounts.bob, now_ts), Err(Error::PermitExpired));
+            let expiry_ts = now_ts + 1_000;
+            assert!(c.set_approval_for_all_until(accounts.bob, expiry_ts).is_ok());
+            assert_eq!(c.operator_expiry_of(accounts.alice, accounts.bob), expiry_ts);
+            assert!(c.is_approved_for_all(accounts.alice, accounts.bob));
+            ink::env::test::set_block_timestamp::<E>(expiry_ts + 1);
+            assert!(!c.is_approved_for_all(accounts.alice, accounts.bob));
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.transfer(accounts.bob, 0), Err(Error::NotApproved));
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.set_approval_for_all(accounts.bob, true).is_ok());
+            assert_eq!(c.operator_expiry_of(accounts.alice, accounts.bob), 0);
+            assert!(c.is_approved_for_all(accounts.alice, accounts.bob));
+        }
+
+        #[ink::test]
+        fn mint_revenue_totals_payments() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
             assert!(c.set_minter(accounts.bob, true).is_ok());
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.set_minter_fee(5).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            ink::env::test::set_value_transferred::<E>(9);
+            assert_eq!(c.mint_n(2), Err(Error::WrongPayment));
+            assert_eq!(c.total_mint_revenue(), 0);
+            ink::env::test::set_value_transferred::<E>(10);
             assert!(c.mint_n(2).is_ok());
-            assert_eq!(c.owner_of(0), Some(accounts.bob));
-            assert_eq!(c.owner_of(1), Some(accounts.bob));
-            assert_eq!(c.balance_of(accounts.bob), 2);
-            let list = c.tokens_of(accounts.bob, 0, 10);
-            assert_eq!(list.len(), 2);
-            assert_eq!(list[0], 0);
-            assert_eq!(list[1], 1);
-            assert!(c.transfer(accounts.charlie, 0).is_ok());
-            assert_eq!(c.owner_of(0), Some(accounts.charlie));
-            assert_eq!(c.balance_of(accounts.bob), 1);
-            assert_eq!(c.balance_of(accounts.charlie), 1);
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.total_mint_revenue(), 20);
+            assert_eq!(c.total_royalties_collected(), 0);
         }
 
         #[ink::test]
-        fn pause_blocks_mint() {
+        fn withdrawals_respect_single_cap() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            let contract_acc = ink::env::test::callee::<E>();
+            assert!(c.set_min_reserve_balance(100).is_ok());
+            ink::env::test::set_account_balance::<E>(contract_acc, 1_000);
+            assert!(c.set_max_single_withdrawal(Some(300)).is_ok());
+            assert_eq!(c.max_single_withdrawal(), Some(300));
+            assert_eq!(c.withdraw(accounts.bob, 301), Err(Error::WithdrawalTooLarge));
+            assert!(c.withdraw(accounts.bob, 300).is_ok());
+            assert!(c.withdraw_all(accounts.bob).is_ok());
+            assert_eq!(c.withdrawable_balance(), 300);
+            assert!(c.set_max_single_withdrawal(None).is_ok());
+            assert!(c.withdraw_all(accounts.bob).is_ok());
+            assert_eq!(c.withdrawable_balance(), 0);
+        }
+
+        #[ink::test]
+        fn wrapped_token_cannot_be_burned() {
             let mut c = NFMoo::new(None);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            assert!(c.set_pause(true).is_ok());
-            assert!(c.set_minter(accounts.bob, true).is_ok());
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            assert!(matches!(c.mint_n(1), Err(Error::Paused)));
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(1).is_ok());
+            assert!(c.grant_role(ROLE_BURNER, accounts.alice).is_ok());
+            // `wrap` needs a live Moo; stand in for its custody record
+            c.wrapped.insert(&0, &accounts.bob);
+            c.wrapped_cnt = 1;
+            assert_eq!(c.burn(0), Err(Error::TokenLocked));
+            assert_eq!(c.wrapped_by(0), Some(accounts.bob));
         }
 
         #[ink::test]
-        fn operator_can_transfer() {
+        fn burned_ids_are_never_minted_again() {
             let mut c = NFMoo::new(None);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            assert!(c.set_minter(accounts.bob, true).is_ok());
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_with_id(accounts.alice, 1).is_ok());
+            assert!(c.set_token_tier(1, Some(2)).is_ok());
+            assert!(c.burn(1).is_ok());
+            assert_eq!(c.tier_of(1), None);
+            assert_eq!(c.mint_with_id(accounts.bob, 1), Err(Error::TokenExists));
+            // the sequential path steps over the burned id as well
+            assert!(c.mint_n(2).is_ok());
+            assert_eq!(c.owner_of(0), Some(accounts.alice));
+            assert_eq!(c.owner_of(1), None);
+            assert_eq!(c.owner_of(2), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn frozen_count_follows_token_through_custody() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_acc = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.transfer_pending(accounts.bob, 0).is_ok());
+            // frozen while escrowed: the lock belongs to whoever holds it
+            assert!(c.freeze_token(0, true).is_ok());
+            assert_eq!(c.locked_balance_of(contract_acc), 1);
+            assert_eq!(c.locked_balance_of(accounts.alice), 0);
+            assert!(c.freeze_token(0, false).is_ok());
+            assert_eq!(c.locked_balance_of(contract_acc), 0);
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(c.accept_transfer(0).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(c.freeze_token(0, true).is_ok());
+            assert_eq!(c.locked_balance_of(accounts.bob), 1);
+            assert_eq!(c.transferable_balance_of(accounts.bob), 0);
+            assert_eq!(c.locked_balance_of(accounts.alice), 0);
+            assert_eq!(c.transferable_balance_of(accounts.alice), 1);
+        }
+
+        #[ink::test]
+        fn pending_transfer_custody_skips_opt_in_hold_and_count() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            assert!(c.mint_n(2).is_ok());
+            assert!(c.set_require_opt_in(true, false).is_ok());
+            assert!(c.set_min_hold_blocks(5).is_ok());
+            for _ in 0..5 {
+                ink::env::test::advance_block::<E>();
+            }
+            assert!(c.transfer_pending(accounts.bob, 0).is_ok());
+            assert!(c.transfer_pending(accounts.bob, 1).is_ok());
+            // cancelling returns the token without restarting the hold or counting a move
+            assert!(c.cancel_transfer(1).is_ok());
+            assert_eq!(c.transfer_count_of(1), 0);
+            assert!(c.transfer_pending(accounts.charlie, 1).is_ok());
+            ink::env::test::set_caller::<E>(accounts.bob);
+            assert_eq!(c.accept_transfer(0), Err(Error::NotOptedIn));
+            assert!(c.opt_in().is_ok());
+            assert!(c.accept_transfer(0).is_ok());
+            assert_eq!(c.transfer_count_of(0), 1);
+            assert_eq!(c.can_transfer(accounts.bob, 0, None), Err(Error::HoldPeriod));
+            // an escrowed token can't be burned out from under its recipient
+            ink::env::test::set_caller::<E>(accounts.alice);
+            assert!(c.grant_role(ROLE_BURNER, accounts.alice).is_ok());
+            assert_eq!(c.burn(1), Err(Error::TokenLocked));
+            assert_eq!(c.pending_transfer_of(1), Some((accounts.alice, accounts.charlie)));
+        }
+
+        #[ink::test]
+        fn batch_write_constants_match_real_paths() {
+            type E = ink::env::DefaultEnvironment;
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<E>();
+            let contract_acc = ink::env::test::callee::<E>();
+            let writes = || ink::env::test::get_contract_storage_rw::<E>(&contract_acc).1 as u64;
+            assert!(c.set_minter(accounts.alice, true).is_ok());
+            let start_cnt = writes();
             assert!(c.mint_n(1).is_ok());
-            assert!(c.set_approval_for_all(accounts.eve, true).is_ok());
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
-            assert!(c.transfer(accounts.charlie, 0).is_ok());
-            assert_eq!(c.owner_of(0), Some(accounts.charlie));
+            let one_cnt = writes();
+            assert!(c.mint_n(2).is_ok());
+            // per-call writes cancel out between the two batches
+            assert_eq!((writes() - one_cnt) - (one_cnt - start_cnt), MINT_WRITES_PER_ITEM);
+            // token 0 of [0, 1, 2]: the sender's list is compacted
+            let before_cnt = writes();
+            assert!(c.transfer(accounts.bob, 0).is_ok());
+            assert_eq!(writes() - before_cnt, TRANSFER_WRITES_PER_ITEM);
+        }
+
+        #[ink::test]
+        fn claim_airdrop_respects_wallet_cap() {
+            let mut c = NFMoo::new(None);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(c.set_max_per_wallet(Some(2)).is_ok());
+            assert!(c.set_airdrop_allocation(accounts.bob, 3).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(c.claim_airdrop(), Err(Error::WalletCapReached));
+            assert_eq!(c.balance_of(accounts.bob), 0);
+            assert_eq!(c.airdrop_allocation_of(accounts.bob), 3);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn sale_royalty_accrues_received_moo_and_withdraws<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let alice_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let bob_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let charlie_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+            let eve_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Eve);
+
+            let mut moo_ctor = moo::MooRef::new();
+            let moo_contract = client
+                .instantiate("moo", &ink_e2e::alice(), &mut moo_ctor)
+                .submit()
+                .await
+                .expect("moo instantiate failed");
+            let mut moo_call = moo_contract.call_builder::<moo::Moo>();
+            let mut nft_ctor = NFMooRef::new(None);
+            let nft_contract = client
+                .instantiate("nfmoo", &ink_e2e::alice(), &mut nft_ctor)
+                .submit()
+                .await
+                .expect("nfmoo instantiate failed");
+            let mut nft_call = nft_contract.call_builder::<NFMoo>();
+
+            // bob holds Moo; Moo then skims 1% of every move and NFMoo is not fee-exempt
+            client.call(&ink_e2e::alice(), &moo_call.set_minter(alice_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.mint(1_000_000)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.enable_trading()).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.transfer(bob_acc, 500_000)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.set_transfer_fee(100, eve_acc)).submit().await?;
+
+            let market_msg = nft_call.set_market_config(Some(moo_contract.account_id), alice_acc, 0);
+            client.call(&ink_e2e::alice(), &market_msg).submit().await?;
+            let royalty_msg = nft_call.set_default_royalty(Some((charlie_acc, 1_000)));
+            client.call(&ink_e2e::alice(), &royalty_msg).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.set_minter(alice_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.mint_n(1)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.list_for_moo(0, 100_000)).submit().await?;
+
+            let approve_msg = moo_call.approve(nft_contract.account_id, 100_000);
+            client.call(&ink_e2e::bob(), &approve_msg).submit().await?;
+            client.call(&ink_e2e::bob(), &nft_call.buy_with_moo(0)).submit().await?;
+
+            // the 10_000 royalty arrived as 9_900; only that is owed
+            let owed_res = client.call(&ink_e2e::charlie(), &nft_call.royalty_owed_of(charlie_acc)).dry_run().await?;
+            assert_eq!(owed_res.return_value(), 9_900);
+            let withdraw_res = client.call(&ink_e2e::charlie(), &nft_call.withdraw_royalties()).submit().await?;
+            assert!(withdraw_res.return_value().is_ok());
+
+            let charlie_res = client.call(&ink_e2e::charlie(), &moo_call.balance_of(charlie_acc)).dry_run().await?;
+            assert_eq!(charlie_res.return_value(), 9_801);
+            let held_msg = moo_call.balance_of(nft_contract.account_id);
+            assert_eq!(client.call(&ink_e2e::alice(), &held_msg).dry_run().await?.return_value(), 0);
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn wrap_then_unwrap_round_trips<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let alice_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+
+            let mut moo_ctor = moo::MooRef::new();
+            let moo_contract = client
+                .instantiate("moo", &ink_e2e::alice(), &mut moo_ctor)
+                .submit()
+                .await
+                .expect("moo instantiate failed");
+            let mut moo_call = moo_contract.call_builder::<moo::Moo>();
+            let mut nft_ctor = NFMooRef::new(None);
+            let nft_contract = client
+                .instantiate("nfmoo", &ink_e2e::alice(), &mut nft_ctor)
+                .submit()
+                .await
+                .expect("nfmoo instantiate failed");
+            let mut nft_call = nft_contract.call_builder::<NFMoo>();
+            let nft_acc = nft_contract.account_id;
+
+            // NFMoo mints on wrap and burns on unwrap (role 3 is Moo's ROLE_BURNER)
+            client.call(&ink_e2e::alice(), &moo_call.set_minter(nft_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &moo_call.grant_role(3, nft_acc)).submit().await?;
+            let market_msg = nft_call.set_market_config(Some(moo_contract.account_id), alice_acc, 0);
+            client.call(&ink_e2e::alice(), &market_msg).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.set_wrap_unit(1_000)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.set_minter(alice_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.mint_n(1)).submit().await?;
+            // custody must work with the opt-in registry and a hold period on
+            client.call(&ink_e2e::alice(), &nft_call.set_require_opt_in(true, false)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.set_min_hold_blocks(1)).submit().await?;
+
+            client.call(&ink_e2e::alice(), &nft_call.wrap(0)).submit().await?;
+            let wrapped_res = client.call(&ink_e2e::alice(), &nft_call.wrapped_by(0)).dry_run().await?;
+            assert_eq!(wrapped_res.return_value(), Some(alice_acc));
+            let moo_res = client.call(&ink_e2e::alice(), &moo_call.balance_of(alice_acc)).dry_run().await?;
+            assert_eq!(moo_res.return_value(), 1_000);
+
+            client.call(&ink_e2e::alice(), &moo_call.approve(nft_acc, 1_000)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.unwrap(0)).submit().await?;
+            let owner_res = client.call(&ink_e2e::alice(), &nft_call.owner_of(0)).dry_run().await?;
+            assert_eq!(owner_res.return_value(), Some(alice_acc));
+            let moo_res = client.call(&ink_e2e::alice(), &moo_call.balance_of(alice_acc)).dry_run().await?;
+            assert_eq!(moo_res.return_value(), 0);
+            let count_res = client.call(&ink_e2e::alice(), &nft_call.transfer_count_of(0)).dry_run().await?;
+            assert_eq!(count_res.return_value(), 0);
+            // nothing is left wrapped, so the unit can change again
+            client.call(&ink_e2e::alice(), &nft_call.set_wrap_unit(2_000)).submit().await?;
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn transfer_and_stake_reverts_on_rejected_callback<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let alice_acc = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+
+            let mut nft_ctor = NFMooRef::new(None);
+            let nft_contract = client
+                .instantiate("nfmoo", &ink_e2e::alice(), &mut nft_ctor)
+                .submit()
+                .await
+                .expect("nfmoo instantiate failed");
+            let mut nft_call = nft_contract.call_builder::<NFMoo>();
+            let mut stake_ctor = receiver_mock::ReceiverMockRef::new(true);
+            let stake_contract = client
+                .instantiate("receiver_mock", &ink_e2e::alice(), &mut stake_ctor)
+                .submit()
+                .await
+                .expect("receiver instantiate failed");
+            let mut stake_call = stake_contract.call_builder::<receiver_mock::ReceiverMock>();
+            let stake_acc = stake_contract.account_id;
+
+            client.call(&ink_e2e::alice(), &nft_call.set_minter(alice_acc, true)).submit().await?;
+            client.call(&ink_e2e::alice(), &nft_call.mint_n(2)).submit().await?;
+
+            // accepted: the token moves and the callback sees the previous owner and data
+            let data_vec = ink::prelude::vec![9u8, 8, 7];
+            let stake_msg = nft_call.transfer_and_stake(stake_acc, 0, data_vec.clone());
+            client.call(&ink_e2e::alice(), &stake_msg).submit().await?;
+            let owner_res = client.call(&ink_e2e::alice(), &nft_call.owner_of(0)).dry_run().await?;
+            assert_eq!(owner_res.return_value(), Some(stake_acc));
+            let seen_res = client.call(&ink_e2e::alice(), &stake_call.last_staked()).dry_run().await?;
+            assert_eq!(seen_res.return_value(), Some((alice_acc, 0, data_vec)));
+
+            // rejected: the whole transfer reverts
+            client.call(&ink_e2e::alice(), &stake_call.set_accept(false)).submit().await?;
+            let reject_msg = nft_call.transfer_and_stake(stake_acc, 1, ink::prelude::vec![1u8]);
+            assert!(client.call(&ink_e2e::alice(), &reject_msg).submit().await.is_err());
+            let owner_res = client.call(&ink_e2e::alice(), &nft_call.owner_of(1)).dry_run().await?;
+            assert_eq!(owner_res.return_value(), Some(alice_acc));
+            let balance_res = client.call(&ink_e2e::alice(), &nft_call.balance_of(stake_acc)).dry_run().await?;
+            assert_eq!(balance_res.return_value(), 1);
+            Ok(())
         }
     }
 }
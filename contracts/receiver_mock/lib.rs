@@ -0,0 +1,62 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Callback target for the e2e tests: records the last callback of each kind it saw and
+/// accepts or rejects them on demand.
+#[ink::contract]
+mod receiver_mock {
+    use ink::prelude::vec::Vec;
+
+    #[ink(storage)]
+    pub struct ReceiverMock {
+        pub(crate) accept_flag: bool,
+        pub(crate) last_received: Option<(AccountId, Balance, Vec<u8>)>,
+        pub(crate) last_staked: Option<(AccountId, u128, Vec<u8>)>,
+    }
+
+    impl ReceiverMock {
+        #[ink(constructor)]
+        pub fn new(accept_flag: bool) -> Self {
+            Self { accept_flag, last_received: None, last_staked: None }
+        }
+
+        #[ink(message)]
+        pub fn set_accept(&mut self, accept_flag: bool) {
+            self.accept_flag = accept_flag;
+        }
+
+        /// Moo's `transfer_and_call` hook. Rejecting reverts the whole transfer.
+        #[ink(message)]
+        pub fn on_received(&mut self, from_acc: AccountId, amount_val: Balance, data_vec: Vec<u8>) -> Result<(), u8> {
+            if !self.accept_flag {
+                return Err(1)
+            }
+            self.last_received = Some((from_acc, amount_val, data_vec));
+            Ok(())
+        }
+
+        /// The last accepted `on_received` call: sender, amount and data, as forwarded.
+        #[ink(message)]
+        pub fn last_received(&self) -> Option<(AccountId, Balance, Vec<u8>)> {
+            self.last_received.clone()
+        }
+
+        /// NFMoo's `transfer_and_stake` hook. Rejecting reverts the whole transfer.
+        #[ink(message)]
+        pub fn on_stake(&mut self, owner_acc: AccountId, token_id: u128, data_vec: Vec<u8>) -> Result<(), u8> {
+            if !self.accept_flag {
+                return Err(1)
+            }
+            self.last_staked = Some((owner_acc, token_id, data_vec));
+            Ok(())
+        }
+
+        /// The last accepted `on_stake` call: previous owner, token and data, as forwarded.
+        #[ink(message)]
+        pub fn last_staked(&self) -> Option<(AccountId, u128, Vec<u8>)> {
+            self.last_staked.clone()
+        }
+    }
+}
+
+#[cfg(feature = "ink-as-dependency")]
+pub use self::receiver_mock::{ReceiverMock, ReceiverMockRef};